@@ -0,0 +1,417 @@
+//! Plays games between bots and rates them
+//!
+//! Usage: `tournament [OPTIONS] BOT BOT [BOT [BOT]]`
+//!
+//! Bots are `random`, `greedy`, `mcts[:iterations]` and `mcts-greedy[:iterations]`,
+//! or external programs with `remote:<command>` and `tcp:<address>` (see [RemotePlayer]),
+//! seats are rotated between games so that each bot plays from every seat.
+//! In duplicate mode, each board is replayed once per seat rotation with the same dice
+//! and development deck, and win rates are computed over these matched sets
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, process, thread};
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use serde::Serialize;
+
+use catan::game::{Game, GameRng, Action, Error, Phase, Notification};
+use catan::state::{State, TricellState, PlayerView, PlayerId};
+use catan::player::{CatanPlayer, Randomy, Greedy, Mcts, MctsConfig, RolloutPolicy, RemotePlayer, RemoteConfig};
+use catan::board::setup;
+use catan::utils::DevelopmentCards;
+
+const USAGE: &str = "\
+Usage: tournament [OPTIONS] BOT BOT [BOT [BOT]]
+Bots: random, greedy, mcts[:iterations], mcts-greedy[:iterations], remote:<command>, tcp:<address>
+Options:
+    --games <N>      Number of games [default: 100]
+    --threads <N>    Games played in parallel [default: 1]
+    --seed <N>       Seed of the first game [default: 0]
+    --max-turns <N>  Games are drawn after this many turns [default: 500]
+    --duplicate      Replays each board, dice and deck with every seat rotation
+    --csv <FILE>     Writes the ratings as CSV
+    --json <FILE>    Writes the ratings and every game as JSON";
+
+/// Initial rating of every bot
+const ELO_START: f64 = 1500.0;
+/// Largest rating change of a pair of bots in a game
+const ELO_K: f64 = 32.0;
+/// Normal quantile of the 95% confidence interval
+const Z_95: f64 = 1.96;
+
+struct Options {
+    games: usize,
+    threads: usize,
+    seed: u64,
+    max_turns: u32,
+    duplicate: bool,
+    csv: Option<String>,
+    json: Option<String>,
+    bots: Vec<String>,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        games: 100,
+        threads: 1,
+        seed: 0,
+        max_turns: 500,
+        duplicate: false,
+        csv: None,
+        json: None,
+        bots: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value of {}", arg));
+        match arg.as_str() {
+            "--games" => options.games = parse_number(&value()?)?,
+            "--threads" => options.threads = parse_number(&value()?)?,
+            "--seed" => options.seed = parse_number(&value()?)?,
+            "--max-turns" => options.max_turns = parse_number(&value()?)?,
+            "--duplicate" => options.duplicate = true,
+            "--csv" => options.csv = Some(value()?),
+            "--json" => options.json = Some(value()?),
+            "--help" | "-h" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => {
+                // Remote bots are only started when games are played
+                if !arg.starts_with("remote:") && !arg.starts_with("tcp:") {
+                    new_bot(&arg, 0)?;
+                }
+                options.bots.push(arg);
+            }
+        }
+    }
+    if options.bots.len() < 2 || options.bots.len() > 4 {
+        return Err(String::from("Between 2 and 4 bots are needed"));
+    }
+    if options.duplicate {
+        // Only complete sets are played
+        let count = options.bots.len();
+        options.games = options.games.div_ceil(count) * count;
+    }
+    if options.threads == 0 {
+        return Err(String::from("At least one thread is needed"));
+    }
+    Ok(options)
+}
+
+fn parse_number<T : std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid number {:?}", value))
+}
+
+fn new_bot(spec: &str, seed: u64) -> Result<Box<dyn CatanPlayer>, String> {
+    if let Some(command) = spec.strip_prefix("remote:") {
+        let mut words = command.split_whitespace();
+        let program = words.next().ok_or_else(|| format!("Missing command in {:?}", spec))?;
+        let args: Vec<&str> = words.collect();
+        let player = RemotePlayer::spawn(program, &args, RemoteConfig::default())
+            .map_err(|error| format!("Couldn't start {:?}: {}", command, error))?;
+        return Ok(Box::new(player));
+    }
+    if let Some(address) = spec.strip_prefix("tcp:") {
+        let player = RemotePlayer::connect(address, RemoteConfig::default())
+            .map_err(|error| format!("Couldn't connect to {:?}: {}", address, error))?;
+        return Ok(Box::new(player));
+    }
+    let mut parts = spec.splitn(2, ':');
+    let name = parts.next().unwrap_or_default();
+    let iterations = match parts.next() {
+        Some(iterations) => Some(parse_number(iterations)?),
+        None => None,
+    };
+    let rollout = match name {
+        "random" if iterations.is_none() => return Ok(Box::new(Randomy::new_player())),
+        "greedy" if iterations.is_none() => return Ok(Box::new(Greedy::new_player())),
+        "mcts" => RolloutPolicy::Random,
+        "mcts-greedy" => RolloutPolicy::Greedy,
+        _ => return Err(format!("Unknown bot {:?}", spec)),
+    };
+    let default = MctsConfig::default();
+    Ok(Box::new(Mcts::new_player(MctsConfig {
+        iterations: iterations.unwrap_or(default.iterations),
+        rollout,
+        seed: Some(seed),
+        ..default
+    })))
+}
+
+/// Player counting turns, and leaving the game once it lasts too long
+struct Seat {
+    player: Box<dyn CatanPlayer>,
+    position: PlayerId,
+    /// Turns of the game, shared by every seat
+    turns: Rc<Cell<u32>>,
+    max_turns: u32,
+}
+
+impl CatanPlayer for Seat {
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView) {
+        self.position = position;
+        self.player.new_game(position, phase, view)
+    }
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
+        if self.turns.get() >= self.max_turns {
+            return Action::Exit;
+        }
+        self.player.pick_action(phase, view)
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        self.player.bad_action(error)
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        if let Notification::ActionPlayed { by, action: Action::EndTurn } = notification {
+            if *by == self.position {
+                self.turns.set(self.turns.get() + 1);
+            }
+        }
+        self.player.notify(notification)
+    }
+
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        self.player.results(view, winner)
+    }
+
+    fn oracle(&self) -> bool {
+        self.player.oracle()
+    }
+}
+
+/// Outcome of a game, bots being identified by their index
+#[derive(Clone, Debug, Serialize)]
+struct GameRecord {
+    game: usize,
+    /// Games of a set share their board, dice and development deck
+    set: usize,
+    seed: u64,
+    /// Bot playing from each seat
+    seats: Vec<usize>,
+    /// `None` if the game was drawn
+    winner: Option<usize>,
+    /// Victory points of each bot
+    victory_points: Vec<u8>,
+    turns: u32,
+}
+
+fn play_game(options: &Options, game: usize) -> GameRecord {
+    let count = options.bots.len();
+    let set = if options.duplicate { game / count } else { game };
+    let seed = options.seed.wrapping_add(set as u64);
+    let turns = Rc::new(Cell::new(0));
+    let mut players = Game::new();
+    for (i, bot) in options.bots.iter().enumerate() {
+        players.add_player(Box::new(Seat {
+            player: new_bot(bot, seed.wrapping_mul(count as u64).wrapping_add(i as u64)).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1)
+            }),
+            position: PlayerId::NONE,
+            turns: turns.clone(),
+            max_turns: options.max_turns,
+        }));
+    }
+    // Seat i is played by bot (i + game) % count
+    let seats: Vec<usize> = (0..count).map(|seat| (seat + game) % count).collect();
+    let mut state: State = setup::random_default::<TricellState, SmallRng>(&mut SmallRng::seed_from_u64(seed), count as u8);
+    let mut rng = GameRng::seed_from_u64(seed);
+    *state.get_development_deck_mut() = rng.development_deck(DevelopmentCards::STARTING_DECK);
+    let winner = match players.play(&mut rng, &mut state, seats.clone()) {
+        Notification::GameFinished { winner } if winner != PlayerId::NONE => Some(seats[winner.to_u8() as usize]),
+        _ => None,
+    };
+    let mut victory_points = vec![0; count];
+    for (seat, bot) in seats.iter().enumerate() {
+        victory_points[*bot] = state.get_player_total_vp(PlayerId::from(seat as u8));
+    }
+    GameRecord {
+        game,
+        set,
+        seed,
+        seats,
+        winner,
+        victory_points,
+        turns: turns.get(),
+    }
+}
+
+fn play_games(options: &Options) -> Vec<GameRecord> {
+    let next = AtomicUsize::new(0);
+    let records = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| loop {
+                let game = next.fetch_add(1, Ordering::SeqCst);
+                if game >= options.games {
+                    break;
+                }
+                let record = play_game(options, game);
+                records.lock().expect("Failed to store game").push(record);
+            });
+        }
+    });
+    let mut records = records.into_inner().expect("Failed to collect games");
+    records.sort_by_key(|record| record.game);
+    records
+}
+
+/// Results of a bot over the tournament
+#[derive(Clone, Debug, Serialize)]
+struct Rating {
+    bot: String,
+    games: usize,
+    wins: usize,
+    win_rate: f64,
+    /// 95% interval of the win rate, Wilson score interval over the games or
+    /// normal interval over the matched sets in duplicate mode
+    win_rate_low: f64,
+    win_rate_high: f64,
+    average_vp: f64,
+    elo: f64,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    ratings: &'a [Rating],
+    draws: usize,
+    average_turns: f64,
+    games: &'a [GameRecord],
+}
+
+fn wilson_interval(wins: usize, games: usize) -> (f64, f64) {
+    if games == 0 {
+        return (0.0, 1.0);
+    }
+    let n = games as f64;
+    let p = wins as f64 / n;
+    let z2 = Z_95 * Z_95;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// 95% normal interval of the mean win rate of matched sets
+fn set_interval(set_win_rates: &[f64]) -> (f64, f64) {
+    let n = set_win_rates.len() as f64;
+    if n < 2.0 {
+        return (0.0, 1.0);
+    }
+    let mean = set_win_rates.iter().sum::<f64>() / n;
+    let variance = set_win_rates.iter().map(|rate| (rate - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let margin = Z_95 * (variance / n).sqrt();
+    ((mean - margin).max(0.0), (mean + margin).min(1.0))
+}
+
+/// Elo ratings updated game after game, each game counting as a match between every pair of bots
+///
+/// The winner beats every other bot, and the losers draw between themselves
+fn elo_ratings(count: usize, records: &[GameRecord]) -> Vec<f64> {
+    let mut elo = vec![ELO_START; count];
+    let k = ELO_K / (count - 1) as f64;
+    for record in records {
+        let mut change = vec![0.0; count];
+        for a in 0..count {
+            for b in 0..count {
+                if a == b {
+                    continue;
+                }
+                let expected = 1.0 / (1.0 + 10f64.powf((elo[b] - elo[a]) / 400.0));
+                let score = match record.winner {
+                    Some(winner) if winner == a => 1.0,
+                    Some(winner) if winner == b => 0.0,
+                    _ => 0.5,
+                };
+                change[a] += k * (score - expected);
+            }
+        }
+        for (elo, change) in elo.iter_mut().zip(change.iter()) {
+            *elo += change;
+        }
+    }
+    elo
+}
+
+fn ratings(options: &Options, records: &[GameRecord]) -> Vec<Rating> {
+    let elo = elo_ratings(options.bots.len(), records);
+    options.bots.iter().enumerate().map(|(bot, name)| {
+        let games = records.len();
+        let wins = records.iter().filter(|record| record.winner == Some(bot)).count();
+        let (win_rate_low, win_rate_high) = if options.duplicate {
+            let set_win_rates: Vec<f64> = records.chunks(options.bots.len())
+                .map(|set| set.iter().filter(|record| record.winner == Some(bot)).count() as f64 / set.len() as f64)
+                .collect();
+            set_interval(&set_win_rates)
+        } else {
+            wilson_interval(wins, games)
+        };
+        let total_vp: u32 = records.iter().map(|record| record.victory_points[bot] as u32).sum();
+        Rating {
+            bot: name.clone(),
+            games,
+            wins,
+            win_rate: if games > 0 { wins as f64 / games as f64 } else { 0.0 },
+            win_rate_low,
+            win_rate_high,
+            average_vp: if games > 0 { total_vp as f64 / games as f64 } else { 0.0 },
+            elo: elo[bot],
+        }
+    }).collect()
+}
+
+fn to_csv(ratings: &[Rating]) -> String {
+    let mut csv = String::from("bot,games,wins,win_rate,win_rate_low,win_rate_high,average_vp,elo\n");
+    for rating in ratings {
+        csv.push_str(&format!("{},{},{},{:.4},{:.4},{:.4},{:.3},{:.1}\n",
+            rating.bot, rating.games, rating.wins, rating.win_rate, rating.win_rate_low, rating.win_rate_high, rating.average_vp, rating.elo
+        ));
+    }
+    csv
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("{}", error);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let records = play_games(&options);
+    let ratings = ratings(&options, &records);
+    let draws = records.iter().filter(|record| record.winner.is_none()).count();
+    let average_turns = if records.is_empty() {
+        0.0
+    } else {
+        records.iter().map(|record| record.turns as f64).sum::<f64>() / records.len() as f64
+    };
+
+    println!("{:<20} {:>6} {:>6} {:>8} {:>17} {:>7} {:>7}", "bot", "games", "wins", "win rate", "95% interval", "avg vp", "elo");
+    for rating in ratings.iter() {
+        println!("{:<20} {:>6} {:>6} {:>8.3} {:>8.3}..{:<7.3} {:>7.2} {:>7.1}",
+            rating.bot, rating.games, rating.wins, rating.win_rate, rating.win_rate_low, rating.win_rate_high, rating.average_vp, rating.elo
+        );
+    }
+    println!("{} games, {} drawn, {:.1} turns on average", records.len(), draws, average_turns);
+
+    if let Some(path) = options.csv.as_ref() {
+        fs::write(path, to_csv(&ratings)).expect("Failed to write CSV");
+    }
+    if let Some(path) = options.json.as_ref() {
+        let report = Report {
+            ratings: &ratings,
+            draws,
+            average_turns,
+            games: &records,
+        };
+        fs::write(path, serde_json::to_string_pretty(&report).expect("Failed to serialize report")).expect("Failed to write JSON");
+    }
+}
@@ -2,7 +2,7 @@ use std::io::{Write, Error};
 use once_cell::sync::Lazy;
 use termion::{cursor, style, color};
 
-use catan::state::{State, PlayerId, PlayerHand};
+use catan::state::{PlayerView, PlayerId, PublicHand};
 use catan::utils::{Resource, Hex, DevelopmentCard};
 use catan::board::Coord;
 
@@ -56,8 +56,8 @@ impl PrettyGridDisplay {
 }
 
 impl GridDisplayable for PrettyGridDisplay {
-    fn display_hex(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, state: &State) ->  Result<(), Error>{
-        let hex = state.get_static_hex(coord).unwrap_or(Hex::Water);
+    fn display_hex(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, view: &PlayerView) ->  Result<(), Error>{
+        let hex = view.get_static_hex(coord).unwrap_or(Hex::Water);
 
         let value = hex.get_num();
         let drawtype = hex.to_draw_type();
@@ -74,10 +74,10 @@ impl GridDisplayable for PrettyGridDisplay {
         Ok(())
     }
 
-    fn display_path(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_i: bool, is_s: bool, state: &State) ->  Result<(), Error> {
+    fn display_path(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_i: bool, is_s: bool, view: &PlayerView) ->  Result<(), Error> {
         let y = if is_i { y } else { y };
 
-        if let Ok(Some(player)) = state.get_dynamic_path(coord) {
+        if let Ok(Some(player)) = view.get_dynamic_path(coord) {
             write!(f, "{}", player_color(player))?;
 
             if is_i {
@@ -94,10 +94,10 @@ impl GridDisplayable for PrettyGridDisplay {
     }
 
 
-    fn display_intersection(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, is_a: bool, state: &State) ->  Result<(), Error> {
+    fn display_intersection(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, is_a: bool, view: &PlayerView) ->  Result<(), Error> {
         let y = if is_a { y } else { y + 1 };
 
-        if let Some(harbor) = state.get_static_harbor(coord).ok() {
+        if let Some(harbor) = view.get_static_harbor(coord).ok() {
             let drawtype = harbor.to_draw_type();
             if drawtype != DrawType::Void {
                 let letter = drawtype.letter();
@@ -109,7 +109,7 @@ impl GridDisplayable for PrettyGridDisplay {
             }
         }
 
-        if let Ok(Some((player, is_city))) = state.get_dynamic_intersection(coord) {
+        if let Ok(Some((player, is_city))) = view.get_dynamic_intersection(coord) {
             write!(f, "{}", player_color(player))?;
 
             if is_city {
@@ -125,8 +125,8 @@ impl GridDisplayable for PrettyGridDisplay {
 const FULL_LINE: &str = "                                ";
 const WIDTH: u16 = 32;
 
-fn lr_la(player: PlayerId, state: &State) -> String {
-    let lr = state.get_longest_road();
+fn lr_la(player: PlayerId, view: &PlayerView) -> String {
+    let lr = view.get_longest_road();
     let lr = if let Some((p, _)) = lr {
         if p == player {
             "LR"
@@ -136,7 +136,7 @@ fn lr_la(player: PlayerId, state: &State) -> String {
     } else {
         "  "
     };
-    let la = state.get_largest_army();
+    let la = view.get_largest_army();
     let la = if let Some((p, _)) = la {
         if p == player {
             "LA"
@@ -149,11 +149,12 @@ fn lr_la(player: PlayerId, state: &State) -> String {
     format!("{} {}", lr, la)
 }
 
-pub fn pretty_public_player_hand(f: &mut dyn Write, player: PlayerId, state: &State) ->  Result<(), Error> {
-    let hand: &PlayerHand = state.get_player_hand(player);
+pub fn pretty_public_player_hand(f: &mut dyn Write, view: &PlayerView) ->  Result<(), Error> {
+    let player = view.player();
+    let hand = view.get_hand();
     let player_color = player_color(player);
     write!(f, "{}{}", player_color, FULL_LINE)?;
-    write!(f, "{}{} [{:>2}]", cursor::Left(10), lr_la(player, state), state.get_player_total_vp(player))?;
+    write!(f, "{}{} [{:>2}]", cursor::Left(10), lr_la(player, view), view.get_total_vp())?;
     write!(f, "{}{} ", cursor::Down(1), cursor::Left(WIDTH))?;
     for resource in Resource::ALL.iter() {
         let resource_draw_type = resource.to_draw_type();
@@ -183,20 +184,20 @@ pub fn pretty_public_player_hand(f: &mut dyn Write, player: PlayerId, state: &St
     Ok(())
 }
 
-pub fn pretty_private_player_hand(f: &mut dyn Write, player: PlayerId, state: &State) ->  Result<(), Error> {
-    let hand: &PlayerHand = state.get_player_hand(player);
+pub fn pretty_private_player_hand(f: &mut dyn Write, player: PlayerId, view: &PlayerView) ->  Result<(), Error> {
+    let hand: PublicHand = view.get_public_hand(player);
     let player_color = player_color(player);
     write!(f, "{}{}", player_color, FULL_LINE)?;
-    write!(f, "{}{} [{:>2}]", cursor::Left(10), lr_la(player, state), state.get_player_total_vp(player))?;
+    write!(f, "{}{} [{:>2}]", cursor::Left(10), lr_la(player, view), view.get_player_public_vp(player))?;
     write!(f, "{}{} ", cursor::Down(1), cursor::Left(WIDTH))?;
     let generic_color = DrawType::GenericHarbor.color();
     write!(f, "         {generic_color}{resource_amount:>2}{player_color}         ",
-        resource_amount = hand.resources.total(),
+        resource_amount = hand.resource_count,
         generic_color = generic_color,
         player_color = player_color)?;
     write!(f, " ")?;
     write!(f, "    {generic_color}{dvp_amount:>2}{player_color}    ",
-        dvp_amount = hand.development_cards.total(),
+        dvp_amount = hand.development_card_count,
         generic_color = generic_color,
         player_color = player_color,
     )?;
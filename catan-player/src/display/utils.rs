@@ -1,7 +1,7 @@
 use std::io::{Write, Error};
 use termion::{cursor, color};
 
-use catan::state::State;
+use catan::state::PlayerView;
 use catan::board::{Coord, DetailedCoordType};
 
 const STEP_WIDTH: u16 = 4;
@@ -43,32 +43,32 @@ fn cursor_position(coord: Coord, half_width: u8, half_height: u8) -> (u16, u16)
 }
 
 pub trait GridDisplayable {
-    fn display_hex(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, state: &State) ->  Result<(), Error>;
+    fn display_hex(&self, x: u16, y: u16, f: &mut dyn Write, coord: Coord, view: &PlayerView) ->  Result<(), Error>;
 
-    fn display_path(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_i: bool, is_s: bool, state: &State) ->  Result<(), Error>;
+    fn display_path(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_i: bool, is_s: bool, view: &PlayerView) ->  Result<(), Error>;
 
-    fn display_intersection(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_a: bool, state: &State) ->  Result<(), Error>;
+    fn display_intersection(&self, x: u16, y: u16,  f: &mut dyn Write, coord: Coord, is_a: bool, view: &PlayerView) ->  Result<(), Error>;
 }
 
-pub fn grid_display<T : GridDisplayable>(displayable: &T, f: &mut dyn Write, state: &State) -> Result<(),Error> {
-    let half_width = state.get_layout().half_width;
-    let half_height = state.get_layout().half_height;
-    for i in 0..state.get_layout().size {
-        let coord = state.get_layout().coord_index(i).unwrap();
+pub fn grid_display<T : GridDisplayable>(displayable: &T, f: &mut dyn Write, view: &PlayerView) -> Result<(),Error> {
+    let half_width = view.get_layout().half_width;
+    let half_height = view.get_layout().half_height;
+    for i in 0..view.get_layout().size {
+        let coord = view.get_layout().coord_index(i).unwrap();
         let (x, y) = cursor_position(coord, half_width, half_height);
         match coord.get_detailed_type() {
-            DetailedCoordType::OHex => displayable.display_hex(x, y, f, coord, state)?,
-            DetailedCoordType::VIntersection => displayable.display_intersection(x, y, f, coord, false, state)?,
-            DetailedCoordType::AIntersection => displayable.display_intersection(x, y, f, coord, true, state)?,
-            DetailedCoordType::ZPath => displayable.display_path(x, y, f, coord, false, false, state)?,
-            DetailedCoordType::SPath => displayable.display_path(x, y, f, coord, false, true, state)?,
-            DetailedCoordType::IPath => displayable.display_path(x, y, f, coord, true, false, state)?,
+            DetailedCoordType::OHex => displayable.display_hex(x, y, f, coord, view)?,
+            DetailedCoordType::VIntersection => displayable.display_intersection(x, y, f, coord, false, view)?,
+            DetailedCoordType::AIntersection => displayable.display_intersection(x, y, f, coord, true, view)?,
+            DetailedCoordType::ZPath => displayable.display_path(x, y, f, coord, false, false, view)?,
+            DetailedCoordType::SPath => displayable.display_path(x, y, f, coord, false, true, view)?,
+            DetailedCoordType::IPath => displayable.display_path(x, y, f, coord, true, false, view)?,
             _ => (),
         };
     };
     write!(f, "{}{}{}",
            color::Fg(color::Reset),
            color::Bg(color::Reset),
-           cursor::Goto(1, state.get_layout().height as u16 * STEP_HEIGHT + STEP_HEIGHT_BUFFER))?;
+           cursor::Goto(1, view.get_layout().height as u16 * STEP_HEIGHT + STEP_HEIGHT_BUFFER))?;
     Ok(())
 }
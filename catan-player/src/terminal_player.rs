@@ -3,14 +3,18 @@ use std::io::{stdout, Stdout, stdin, Write};
 use termion::clear;
 //use termion::screen::AlternateScreen;
 
-use catan::state::{State, PlayerId};
+use catan::state::{PlayerView, PlayerId};
 use catan::game::{Action, Error, Phase, Notification};
 use catan::player::CatanPlayer;
+use catan::board::opening::{rank_openings, opening_road, unconnected_settlement, OpeningWeights};
 
 use crate::display::utils::grid_display;
 use crate::display::{PrettyGridDisplay, pretty_public_player_hand, pretty_private_player_hand};
 use super::action_parser::{parse_action, parse_help};
 
+/// Number of placements suggested during the initial placement
+const HINT_COUNT: usize = 3;
+
 pub struct TerminalPlayer {
     screen: Stdout,
     player: PlayerId,
@@ -29,14 +33,14 @@ impl TerminalPlayer {
         }
     }
 
-    pub fn write_state(&mut self, state: &State) {
-        grid_display(&PrettyGridDisplay::INSTANCE, &mut self.screen, state).expect("Failed to draw grid");
-        for i in 0..state.player_count() {
+    pub fn write_state(&mut self, view: &PlayerView) {
+        grid_display(&PrettyGridDisplay::INSTANCE, &mut self.screen, view).expect("Failed to draw grid");
+        for i in 0..view.player_count() {
             let player = PlayerId::from(i);
             if player == self.player {
-                pretty_public_player_hand(&mut self.screen, player, state).expect("Failed to draw player hand");
+                pretty_public_player_hand(&mut self.screen, view).expect("Failed to draw player hand");
             } else {
-                pretty_private_player_hand(&mut self.screen, player, state).expect("Failed to draw player hand");
+                pretty_private_player_hand(&mut self.screen, player, view).expect("Failed to draw player hand");
 
             }
             writeln!(&mut self.screen).expect("Failed to return line");
@@ -49,6 +53,35 @@ impl TerminalPlayer {
         }
     }
 
+    /// Suggests placements from the opening optimizer during the initial placement
+    pub fn write_hints(&mut self, phase: &Phase, view: &PlayerView) {
+        let weights = OpeningWeights::default();
+        match phase {
+            Phase::InitialPlacement { placing_road: true, .. } => {
+                let road = unconnected_settlement(view, self.player)
+                    .and_then(|settlement| opening_road(view, self.player, settlement, &weights));
+                if let Some(road) = road {
+                    writeln!(self.screen, "[HINT] R {},{}", road.x, road.y).expect("Failed to write hint");
+                }
+            }
+            Phase::InitialPlacement { .. } => {
+                let mut settlements = Vec::new();
+                for candidate in rank_openings(view, self.player, view.player_count(), &weights) {
+                    if settlements.len() == HINT_COUNT {
+                        break;
+                    }
+                    if !settlements.contains(&candidate.settlement) {
+                        settlements.push(candidate.settlement);
+                        writeln!(self.screen, "[HINT] S {},{} then R {},{} ({:.1})",
+                            candidate.settlement.x, candidate.settlement.y, candidate.road.x, candidate.road.y, candidate.value
+                        ).expect("Failed to write hint");
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn write_error(&mut self) {
         if let Some(error) = self.bad_action {
             writeln!(self.screen, "[ERROR] {:?}", error).expect("Failed to write error");
@@ -57,20 +90,22 @@ impl TerminalPlayer {
 }
 
 impl CatanPlayer for TerminalPlayer {
-    fn new_game(&mut self, position: PlayerId, _: &State) {
+    fn new_game(&mut self, position: PlayerId, _: &Phase, _: &PlayerView) {
         self.player = position;
         write!(self.screen, "{clear}", clear = clear::All).unwrap();
         writeln!(self.screen, "[New game]").unwrap();
         self.screen.flush().unwrap();
     }
 
-    fn pick_action(&mut self, phase: &Phase, state: &State) -> Action {
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
         write!(self.screen, "{clear}", clear = clear::All).expect("Failed to clear screen");
         // Displays state
-        self.write_state(state);
+        self.write_state(view);
         // Displays notifications
         self.write_notifications();
         writeln!(self.screen, "{:?}", phase).unwrap();
+        // Displays hints
+        self.write_hints(phase, view);
         loop {
             // Displays previous error
             self.write_error();
@@ -104,8 +139,8 @@ impl CatanPlayer for TerminalPlayer {
         }
     }
 
-    fn results(&mut self, state: &State, winner: PlayerId) {
-        self.write_state(state);
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        self.write_state(view);
         writeln!(self.screen, "Winner : {:?}", winner).unwrap();
     }
 }
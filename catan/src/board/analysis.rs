@@ -0,0 +1,171 @@
+use serde::Serialize;
+
+use crate::utils::{Coord, Hex, LandHex, Harbor, Resource};
+use crate::state::{StateTrait, PlayerView};
+use crate::board::utils::topology::Topology;
+use crate::board::Layout;
+
+/// Number of spots given in [best_openings](BoardReport::best_openings)
+pub const OPENING_COUNT: usize = 10;
+
+/// Anything with a static board that can be analysed
+pub trait StaticBoard {
+    fn board_layout(&self) -> &Layout;
+
+    fn board_hex(&self, coord: Coord) -> Hex;
+
+    fn board_harbor(&self, coord: Coord) -> Harbor;
+}
+
+impl<T : StateTrait + ?Sized> StaticBoard for T {
+    fn board_layout(&self) -> &Layout {
+        self.get_layout()
+    }
+
+    fn board_hex(&self, coord: Coord) -> Hex {
+        self.get_static_hex(coord).unwrap_or(Hex::Water)
+    }
+
+    fn board_harbor(&self, coord: Coord) -> Harbor {
+        self.get_static_harbor(coord).unwrap_or(Harbor::None)
+    }
+}
+
+impl StaticBoard for PlayerView<'_> {
+    fn board_layout(&self) -> &Layout {
+        self.get_layout()
+    }
+
+    fn board_hex(&self, coord: Coord) -> Hex {
+        self.get_static_hex(coord).unwrap_or(Hex::Water)
+    }
+
+    fn board_harbor(&self, coord: Coord) -> Harbor {
+        self.get_static_harbor(coord).unwrap_or(Harbor::None)
+    }
+}
+
+/// Production of a settlement placed on an intersection
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct IntersectionValue {
+    pub intersection: Coord,
+    /// Pips of the surrounding hexes
+    pub pips: u8,
+    /// Pips of the surrounding hexes, for each resource
+    pub production: [u8; Resource::COUNT],
+    /// Number of different resources produced
+    pub diversity: u8,
+    pub harbor: Harbor,
+}
+
+/// How useful a harbor is
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct HarborValue {
+    pub harbor: Harbor,
+    pub intersections: Vec<Coord>,
+    /// Best production of the intersections giving access to the harbor
+    pub access_pips: u8,
+    /// Pips on the whole board of the resources that can be traded at this harbor
+    pub traded_pips: u16,
+}
+
+/// Analysis of a static board
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct BoardReport {
+    /// Total pips of each resource
+    pub pips: [u8; Resource::COUNT],
+    /// Number of tiles of each resource
+    pub tiles: [u8; Resource::COUNT],
+    /// How much scarcer than average each resource is, negative if it is abundant
+    pub scarcity: [f32; Resource::COUNT],
+    pub intersections: Vec<IntersectionValue>,
+    /// Most productive intersections, the most diverse first when tied
+    pub best_openings: Vec<IntersectionValue>,
+    pub harbors: Vec<HarborValue>,
+}
+
+impl BoardReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed writing report")
+    }
+}
+
+pub fn intersection_value<B : StaticBoard + ?Sized>(board: &B, intersection: Coord) -> IntersectionValue {
+    let mut production = [0; Resource::COUNT];
+    for hex in Coord::TOPOLOGY.intersection_hex_neighbours(intersection).unwrap_or_default() {
+        if !board.board_layout().hexes.contains(&hex) {
+            continue;
+        }
+        let tile = board.board_hex(hex);
+        if let Hex::Land(LandHex::Prod(resource, _)) = tile {
+            production[resource.to_usize()] += tile.pips();
+        }
+    }
+    IntersectionValue {
+        intersection,
+        pips: production.iter().sum(),
+        production,
+        diversity: production.iter().filter(|pips| **pips > 0).count() as u8,
+        harbor: board.board_harbor(intersection),
+    }
+}
+
+pub fn analyse<B : StaticBoard + ?Sized>(board: &B) -> BoardReport {
+    let layout = board.board_layout();
+    let mut pips = [0; Resource::COUNT];
+    let mut tiles = [0; Resource::COUNT];
+    for hex in layout.hexes.iter() {
+        let tile = board.board_hex(*hex);
+        if let Hex::Land(LandHex::Prod(resource, _)) = tile {
+            pips[resource.to_usize()] += tile.pips();
+            tiles[resource.to_usize()] += 1;
+        }
+    }
+    let mean = pips.iter().map(|pips| *pips as f32).sum::<f32>() / Resource::COUNT as f32;
+    let mut scarcity = [0.0; Resource::COUNT];
+    for (scarcity, pips) in scarcity.iter_mut().zip(pips.iter()) {
+        if mean > 0.0 {
+            *scarcity = 1.0 - *pips as f32 / mean;
+        }
+    }
+
+    let intersections: Vec<IntersectionValue> = layout.intersections.iter()
+        .map(|intersection| intersection_value(board, *intersection))
+        .collect();
+    let mut best_openings = intersections.clone();
+    best_openings.sort_by(|a, b| b.pips.cmp(&a.pips).then(b.diversity.cmp(&a.diversity)));
+    best_openings.truncate(OPENING_COUNT);
+
+    let mut harbors: Vec<HarborValue> = Vec::new();
+    for value in intersections.iter() {
+        if value.harbor == Harbor::None {
+            continue;
+        }
+        // Both intersections of a harbor are neighbours
+        let neighbours = Coord::TOPOLOGY.intersection_intersection_neighbours(value.intersection).unwrap_or_default();
+        match harbors.iter_mut().find(|harbor| harbor.harbor == value.harbor && harbor.intersections.iter().any(|i| neighbours.contains(i))) {
+            Some(harbor) => {
+                harbor.intersections.push(value.intersection);
+                harbor.access_pips = harbor.access_pips.max(value.pips);
+            }
+            None => harbors.push(HarborValue {
+                harbor: value.harbor,
+                intersections: vec![value.intersection],
+                access_pips: value.pips,
+                traded_pips: match value.harbor {
+                    Harbor::Special(resource) => pips[resource.to_usize()] as u16,
+                    _ => pips.iter().map(|pips| *pips as u16).sum(),
+                },
+            }),
+        }
+    }
+
+    BoardReport {
+        pips,
+        tiles,
+        scarcity,
+        intersections,
+        best_openings,
+        harbors,
+    }
+}
@@ -0,0 +1,198 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::{LayoutSpec, TileSpec, HarborSpec, SpecError, DEFAULT_SPEC, is_coastal};
+use crate::board::utils::{Axial, HexDirection};
+use crate::board::utils::topology::Topology;
+use crate::utils::{Coord, Resource};
+
+/// Shape of procedurally generated boards
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct IslandParams {
+    /// Total number of land hexes
+    pub land_hexes: usize,
+    /// Number of separate landmasses, with water between them
+    pub islands: usize,
+    /// Every hex is at most this many steps away from the center
+    pub max_radius: u8,
+    pub harbors: usize,
+    /// Number of shapes generated before giving up
+    pub max_attempts: u32,
+}
+
+impl IslandParams {
+    /// Largest radius for which the coords around the board still fit in a [Coord]
+    pub const MAX_RADIUS: u8 = 30;
+}
+
+impl Default for IslandParams {
+    fn default() -> Self {
+        IslandParams {
+            land_hexes: 19,
+            islands: 1,
+            max_radius: 3,
+            harbors: 9,
+            max_attempts: 1000,
+        }
+    }
+}
+
+/// Reasons a procedural board couldn't be generated
+#[derive(Clone, PartialEq, Debug)]
+pub enum IslandError {
+    /// There must be at least one island, one land hex per island, and a radius up to [MAX_RADIUS](IslandParams::MAX_RADIUS)
+    InvalidParams,
+    /// No shape fitted in the radius, or had enough coasts for the harbors
+    NoRoom,
+    Spec(SpecError),
+}
+
+impl From<SpecError> for IslandError {
+    fn from(error: SpecError) -> IslandError {
+        IslandError::Spec(error)
+    }
+}
+
+/// Generates a random landmass, with a tile pool, number tokens and harbors to match
+///
+/// Tiles keep the proportions of the standard board, with a desert for every 19 hexes,
+/// and the number tokens of the standard board are repeated as many times as needed
+pub fn random_island_spec<R : Rng>(rng: &mut R, params: &IslandParams) -> Result<LayoutSpec, IslandError> {
+    if params.islands == 0 || params.land_hexes < params.islands || params.max_radius > IslandParams::MAX_RADIUS {
+        return Err(IslandError::InvalidParams);
+    }
+    for _ in 0..params.max_attempts {
+        let hexes = match grow_islands(rng, params) {
+            Some(hexes) => hexes,
+            None => continue,
+        };
+        let harbors = match pick_harbors(rng, &hexes, params.harbors)? {
+            Some(harbors) => harbors,
+            None => continue,
+        };
+        let tiles = tile_pool(hexes.len());
+        let producing = tiles.iter().filter(|tile| tile.resource().is_some()).count();
+        let spec = LayoutSpec {
+            hexes: hexes.iter().map(|hex| [hex.x, hex.y]).collect(),
+            tiles,
+            tokens: DEFAULT_SPEC.tokens.iter().cycle().take(producing).cloned().collect(),
+            track: hexes.iter().map(|hex| [hex.x, hex.y]).collect(),
+            harbors: harbors.iter().map(|path| [path.x, path.y]).collect(),
+            harbor_tiles: harbor_pool(rng, harbors.len()),
+        };
+        spec.validate()?;
+        return Ok(spec);
+    }
+    Err(IslandError::NoRoom)
+}
+
+/// Grows each island from a seed, never letting two islands touch
+fn grow_islands<R : Rng>(rng: &mut R, params: &IslandParams) -> Option<Vec<Coord>> {
+    let area = Axial::ZERO.spiral(params.max_radius);
+    let mut owner: Vec<Option<usize>> = vec![None; area.len()];
+    let position = |hex: Axial| area.iter().position(|other| *other == hex);
+    let touches_other = |owner: &[Option<usize>], hex: Axial, island: usize| {
+        HexDirection::ALL.iter().any(|direction| {
+            position(hex.neighbour(*direction))
+                .and_then(|i| owner[i])
+                .is_some_and(|other| other != island)
+        })
+    };
+    let mut sizes = vec![params.land_hexes / params.islands; params.islands];
+    for size in sizes.iter_mut().take(params.land_hexes % params.islands) {
+        *size += 1;
+    }
+    // Seeds
+    let mut islands: Vec<Vec<Axial>> = Vec::new();
+    for island in 0..params.islands {
+        let candidates: Vec<Axial> = area.iter().cloned()
+            .filter(|hex| {
+                let i = position(*hex).unwrap();
+                owner[i].is_none() && !touches_other(&owner, *hex, island)
+            })
+            .collect();
+        let seed = *candidates.choose(rng)?;
+        owner[position(seed).unwrap()] = Some(island);
+        islands.push(vec![seed]);
+    }
+    // Growth, one hex at a time to each island in turn
+    for step in 1..sizes.iter().cloned().max().unwrap_or(0) {
+        for (island, hexes) in islands.iter_mut().enumerate() {
+            if step >= sizes[island] {
+                continue;
+            }
+            let mut frontier: Vec<Axial> = Vec::new();
+            for hex in hexes.iter() {
+                for direction in HexDirection::ALL.iter() {
+                    let next = hex.neighbour(*direction);
+                    if let Some(i) = position(next) {
+                        if owner[i].is_none() && !touches_other(&owner, next, island) && !frontier.contains(&next) {
+                            frontier.push(next);
+                        }
+                    }
+                }
+            }
+            let next = *frontier.choose(rng)?;
+            owner[position(next).unwrap()] = Some(island);
+            hexes.push(next);
+        }
+    }
+    let mut hexes: Vec<Coord> = islands.into_iter().flatten().map(Axial::to_coord).collect();
+    hexes.sort();
+    Some(hexes)
+}
+
+/// Picks coastal paths for the harbors, so that no two harbors share an intersection
+fn pick_harbors<R : Rng>(rng: &mut R, hexes: &[Coord], count: usize) -> Result<Option<Vec<Coord>>, SpecError> {
+    let mut coasts = Vec::new();
+    for hex in hexes.iter() {
+        for path in Coord::TOPOLOGY.hex_path_neighbours(*hex)? {
+            if is_coastal(hexes, path)? {
+                coasts.push(path);
+            }
+        }
+    }
+    coasts.shuffle(rng);
+    let mut harbors: Vec<Coord> = Vec::new();
+    let mut used: Vec<Coord> = Vec::new();
+    for path in coasts {
+        if harbors.len() == count {
+            break;
+        }
+        let intersections = Coord::TOPOLOGY.path_intersection_neighbours(path)?;
+        if intersections.iter().any(|intersection| used.contains(intersection)) {
+            continue;
+        }
+        used.extend(intersections);
+        harbors.push(path);
+    }
+    harbors.sort();
+    Ok(if harbors.len() == count { Some(harbors) } else { None })
+}
+
+fn tile_pool(hexes: usize) -> Vec<TileSpec> {
+    let deserts = hexes.div_ceil(19);
+    let producing = hexes - deserts;
+    // Standard proportions, and the order in which the remaining tiles are given
+    let standard = [(TileSpec::Lumber, 4), (TileSpec::Grain, 4), (TileSpec::Wool, 4), (TileSpec::Brick, 3), (TileSpec::Ore, 3)];
+    let mut counts: Vec<usize> = standard.iter().map(|(_, count)| count * producing / 18).collect();
+    let remaining = producing - counts.iter().sum::<usize>();
+    let kinds = counts.len();
+    for i in 0..remaining {
+        counts[i % kinds] += 1;
+    }
+    let mut tiles = vec![TileSpec::Desert; deserts];
+    for ((tile, _), count) in standard.iter().zip(counts) {
+        tiles.extend(vec![*tile; count]);
+    }
+    tiles
+}
+
+fn harbor_pool<R : Rng>(rng: &mut R, harbors: usize) -> Vec<HarborSpec> {
+    let specials = ((5 * harbors + 4) / 9).min(Resource::COUNT);
+    let mut resources = vec![HarborSpec::Brick, HarborSpec::Lumber, HarborSpec::Ore, HarborSpec::Grain, HarborSpec::Wool];
+    resources.shuffle(rng);
+    resources.truncate(specials);
+    resources.extend(vec![HarborSpec::Generic; harbors - specials]);
+    resources
+}
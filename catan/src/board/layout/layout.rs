@@ -1,5 +1,10 @@
+use std::collections::BTreeSet;
+
 use super::{Coord, Error};
+use crate::board::CoordType;
+use crate::board::utils::topology::{Topology, RawTopology, Neighbours, TopologyResult};
 
+#[derive(Clone)]
 pub struct Layout {
     pub half_width: u8,
     pub half_height: u8,
@@ -9,6 +14,19 @@ pub struct Layout {
     pub hexes: Vec<Coord>,
     pub paths: Vec<Coord>,
     pub intersections: Vec<Coord>,
+    /// Hex, path and intersection neighbours of every coord of the grid, by flat index
+    adjacency: Vec<[Neighbours; 3]>,
+    /// Position of every coord of the grid in `hexes`, `paths` or `intersections`, by flat index
+    positions: Vec<Option<u16>>,
+}
+
+fn type_index(coord_type: CoordType) -> Option<usize> {
+    match coord_type {
+        CoordType::Hex => Some(0),
+        CoordType::Path => Some(1),
+        CoordType::Intersection => Some(2),
+        CoordType::Void => None,
+    }
 }
 
 impl Layout {
@@ -17,7 +35,7 @@ impl Layout {
         let half_width = 2*half_height;
         let width = 2*half_width+1;
         let height = 2*half_height+1;
-        Layout {
+        let mut layout = Layout {
             half_width,
             half_height,
             width,
@@ -26,7 +44,48 @@ impl Layout {
             hexes,
             paths,
             intersections,
+            adjacency: Vec::new(),
+            positions: Vec::new(),
+        };
+        let mut positions = vec![None; layout.size];
+        for coords in [&layout.hexes, &layout.paths, &layout.intersections].iter() {
+            for (position, coord) in coords.iter().enumerate() {
+                if let Some(flat) = layout.grid_index(*coord) {
+                    positions[flat] = Some(position as u16);
+                }
+            }
+        }
+        layout.positions = positions;
+        layout.adjacency = (0..layout.size).map(|flat| {
+            let coord = layout.coord_index(flat).expect("Flat index is in the grid");
+            let mut adjacency = [Neighbours::EMPTY; 3];
+            if coord.get_type() != CoordType::Void {
+                for (neighbour_type, neighbours) in [CoordType::Hex, CoordType::Path, CoordType::Intersection].iter().zip(adjacency.iter_mut()) {
+                    *neighbours = Coord::TOPOLOGY.neighbours(coord, coord.get_type(), *neighbour_type)
+                        .expect("Every coord type has neighbours")
+                        .filtered(|neighbour| layout.contains_coord(neighbour));
+                }
+            }
+            adjacency
+        }).collect();
+        layout
+    }
+
+    /// Layout containing the given hexes, and the paths and intersections around them
+    ///
+    /// The ray is the smallest one for which every hex fits
+    pub fn from_hexes(hexes: Vec<Coord>) -> Result<Layout, Error> {
+        let mut paths = BTreeSet::<Coord>::new();
+        let mut intersections = BTreeSet::<Coord>::new();
+        let mut ray = 0;
+        for hex in hexes.iter() {
+            paths.extend(Coord::TOPOLOGY.hex_path_neighbours(*hex)?);
+            intersections.extend(Coord::TOPOLOGY.hex_intersection_neighbours(*hex)?);
+            let x = hex.x.unsigned_abs();
+            let y = hex.y.unsigned_abs();
+            ray = ray.max(y / 2).max((x + y) / 4);
         }
+        Ok(Layout::new(ray, hexes, paths.into_iter().collect(), intersections.into_iter().collect()))
     }
 
     pub fn flat_index(&self, coord: Coord) -> Result<usize, Error> {
@@ -55,9 +114,38 @@ impl Layout {
         if flat > self.size {
             Err(Error::OutOfBoard)
         } else {
-            let x = (flat % self.width as usize) as i8 - self.half_width as i8;
-            let y = (flat / self.width as usize) as i8 - self.half_height as i8;
-            Ok(Coord::new(x,y))
+            let x = (flat % self.width as usize) as isize - self.half_width as isize;
+            let y = (flat / self.width as usize) as isize - self.half_height as isize;
+            Ok(Coord::new(x as i8, y as i8))
+        }
+    }
+
+    fn grid_index(&self, coord: Coord) -> Option<usize> {
+        let x = coord.x as isize + self.half_width as isize;
+        let y = coord.y as isize + self.half_height as isize;
+        if 0 <= x && x < self.width as isize && 0 <= y && y < self.height as isize {
+            Some((x + y * self.width as isize) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Position of a coord in `hexes`, `paths` or `intersections`, depending on its type
+    pub fn position(&self, coord: Coord) -> Option<usize> {
+        self.positions[self.grid_index(coord)?].map(|position| position as usize)
+    }
+
+    /// Neighbours of a coord that are inside the layout, read from precomputed tables
+    pub fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
+        let received = coord.get_type();
+        if received != center_type {
+            return Err(Error::WrongCoordType { expected: center_type, received });
+        }
+        match (self.grid_index(coord), type_index(neighbour_type)) {
+            (Some(flat), Some(index)) => Ok(self.adjacency[flat][index]),
+            (None, Some(_)) => Ok(Coord::TOPOLOGY.neighbours(coord, center_type, neighbour_type)?
+                .filtered(|neighbour| self.contains_coord(neighbour))),
+            (_, None) => Err(Error::InvalidNeighbourTypes { center: center_type, neighbours: neighbour_type }),
         }
     }
 
@@ -68,6 +156,12 @@ impl Layout {
     }
 }
 
+impl RawTopology for Layout {
+    fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
+        Layout::neighbours(self, coord, center_type, neighbour_type)
+    }
+}
+
 #[allow(dead_code)]
 pub fn print_layout(layout: &Layout) {
     println!("hexes {:?}", layout.hexes);
@@ -1,11 +1,12 @@
 mod layout;
+mod spec;
 mod default;
+mod islands;
 
 pub use layout::{Layout, print_layout};
-pub use default::DEFAULT;
+pub use spec::{LayoutSpec, TileSpec, HarborSpec, SpecError};
+pub(crate) use spec::is_coastal;
+pub use default::{DEFAULT, DEFAULT_SPEC};
+pub use islands::{IslandParams, IslandError, random_island_spec};
 
 use super::{Coord, Error};
-
-const fn c(y:i8, x:i8) -> Coord {
-    Coord::new(x,y)
-}
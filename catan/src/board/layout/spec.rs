@@ -0,0 +1,223 @@
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+
+use super::{Layout, Coord, Error};
+use crate::utils::{Hex, LandHex, Harbor, Resource, CoordType};
+use crate::board::utils::topology::Topology;
+
+/// Land tile of a layout's pool
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileSpec {
+    Brick,
+    Lumber,
+    Ore,
+    Grain,
+    Wool,
+    Desert,
+}
+
+/// Harbor tile of a layout's pool
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HarborSpec {
+    Brick,
+    Lumber,
+    Ore,
+    Grain,
+    Wool,
+    Generic,
+}
+
+impl TileSpec {
+    pub fn resource(self) -> Option<Resource> {
+        match self {
+            TileSpec::Brick => Some(Resource::Brick),
+            TileSpec::Lumber => Some(Resource::Lumber),
+            TileSpec::Ore => Some(Resource::Ore),
+            TileSpec::Grain => Some(Resource::Grain),
+            TileSpec::Wool => Some(Resource::Wool),
+            TileSpec::Desert => None,
+        }
+    }
+
+    /// The hex this tile becomes, with a number token if it produces
+    pub fn hex(self, token: u8) -> Hex {
+        match self.resource() {
+            Some(resource) => Hex::Land(LandHex::Prod(resource, token)),
+            None => Hex::Land(LandHex::Desert),
+        }
+    }
+}
+
+impl HarborSpec {
+    pub fn harbor(self) -> Harbor {
+        match self {
+            HarborSpec::Brick => Harbor::Special(Resource::Brick),
+            HarborSpec::Lumber => Harbor::Special(Resource::Lumber),
+            HarborSpec::Ore => Harbor::Special(Resource::Ore),
+            HarborSpec::Grain => Harbor::Special(Resource::Grain),
+            HarborSpec::Wool => Harbor::Special(Resource::Wool),
+            HarborSpec::Generic => Harbor::Generic,
+        }
+    }
+}
+
+/// Reasons a layout specification can't be used
+#[derive(Clone, PartialEq, Debug)]
+pub enum SpecError {
+    /// The file couldn't be read
+    Io(String),
+    /// The file couldn't be parsed
+    Parse(String),
+    /// A coordinate isn't of the expected type
+    Coord(Error),
+    /// There isn't one tile per hex
+    TileCount {
+        hexes: usize,
+        tiles: usize,
+    },
+    /// There isn't one number token per producing tile
+    TokenCount {
+        producing: usize,
+        tokens: usize,
+    },
+    /// A number token isn't a dice roll from 2 to 12 other than 7
+    InvalidToken(u8),
+    /// The track doesn't go through every hex exactly once
+    Track,
+    /// There isn't one harbor tile per harbor
+    HarborCount {
+        harbors: usize,
+        tiles: usize,
+    },
+    /// A harbor isn't on a path between a land hex and the sea
+    HarborNotCoastal(Coord),
+}
+
+impl From<Error> for SpecError {
+    fn from(error: Error) -> SpecError {
+        SpecError::Coord(error)
+    }
+}
+
+/// Description of a board, that can be loaded from a TOML or JSON file
+///
+/// Coordinates are written `[x, y]`. Tiles are shuffled over the hexes, then number tokens
+/// are placed in order along the track, skipping the deserts. Harbor tiles are shuffled over the harbors
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LayoutSpec {
+    /// Land hexes
+    pub hexes: Vec<[i8; 2]>,
+    pub tiles: Vec<TileSpec>,
+    pub tokens: Vec<u8>,
+    pub track: Vec<[i8; 2]>,
+    /// Coastal paths with a harbor
+    pub harbors: Vec<[i8; 2]>,
+    pub harbor_tiles: Vec<HarborSpec>,
+}
+
+fn coords(coords: &[[i8; 2]]) -> Vec<Coord> {
+    coords.iter().map(|[x, y]| Coord::new(*x, *y)).collect()
+}
+
+impl LayoutSpec {
+    pub fn from_toml(text: &str) -> Result<LayoutSpec, SpecError> {
+        toml::from_str(text).map_err(|error| SpecError::Parse(error.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<LayoutSpec, SpecError> {
+        serde_json::from_str(text).map_err(|error| SpecError::Parse(error.to_string()))
+    }
+
+    /// Reads a `.json` file, or a TOML file otherwise, and validates it
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<LayoutSpec, SpecError> {
+        let text = std::fs::read_to_string(&path).map_err(|error| SpecError::Io(error.to_string()))?;
+        let spec = match path.as_ref().extension() {
+            Some(extension) if extension == "json" => LayoutSpec::from_json(&text)?,
+            _ => LayoutSpec::from_toml(&text)?,
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("Failed writing layout")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed writing layout")
+    }
+
+    pub fn hex_coords(&self) -> Vec<Coord> {
+        coords(&self.hexes)
+    }
+
+    pub fn track_coords(&self) -> Vec<Coord> {
+        coords(&self.track)
+    }
+
+    pub fn harbor_coords(&self) -> Vec<Coord> {
+        coords(&self.harbors)
+    }
+
+    pub fn layout(&self) -> Result<Layout, SpecError> {
+        for hex in self.hex_coords() {
+            check_type(hex, CoordType::Hex)?;
+        }
+        Ok(Layout::from_hexes(self.hex_coords())?)
+    }
+
+    /// Checks that the pools fit the board
+    pub fn validate(&self) -> Result<(), SpecError> {
+        let hexes = self.hex_coords();
+        for hex in hexes.iter() {
+            check_type(*hex, CoordType::Hex)?;
+        }
+        if self.tiles.len() != hexes.len() {
+            return Err(SpecError::TileCount { hexes: hexes.len(), tiles: self.tiles.len() });
+        }
+        let producing = self.tiles.iter().filter(|tile| tile.resource().is_some()).count();
+        if self.tokens.len() != producing {
+            return Err(SpecError::TokenCount { producing, tokens: self.tokens.len() });
+        }
+        if let Some(token) = self.tokens.iter().find(|token| !(2..=12).contains(*token) || **token == 7) {
+            return Err(SpecError::InvalidToken(*token));
+        }
+        let mut track = self.track_coords();
+        let mut sorted_hexes = hexes.clone();
+        track.sort();
+        sorted_hexes.sort();
+        track.dedup();
+        if track != sorted_hexes || track.len() != self.track.len() {
+            return Err(SpecError::Track);
+        }
+        if self.harbor_tiles.len() != self.harbors.len() {
+            return Err(SpecError::HarborCount { harbors: self.harbors.len(), tiles: self.harbor_tiles.len() });
+        }
+        for harbor in self.harbor_coords() {
+            if !is_coastal(&hexes, harbor)? {
+                return Err(SpecError::HarborNotCoastal(harbor));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_type(coord: Coord, expected: CoordType) -> Result<(), SpecError> {
+    let received = coord.get_type();
+    if received == expected {
+        Ok(())
+    } else {
+        Err(SpecError::Coord(Error::WrongCoordType { expected, received }))
+    }
+}
+
+/// Is the path between exactly one of the land hexes and the sea
+pub(crate) fn is_coastal(hexes: &[Coord], path: Coord) -> Result<bool, SpecError> {
+    check_type(path, CoordType::Path)?;
+    let land = Coord::TOPOLOGY.path_hex_neighbours(path)?.into_iter()
+        .filter(|hex| hexes.contains(hex))
+        .count();
+    Ok(land == 1)
+}
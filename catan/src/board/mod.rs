@@ -3,6 +3,10 @@ pub mod utils;
 pub mod map;
 pub mod layout;
 pub mod setup;
+pub mod analysis;
+pub mod symmetry;
+pub mod reach;
+pub mod opening;
 
 pub use layout::Layout;
 pub use error::Error;
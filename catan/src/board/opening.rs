@@ -0,0 +1,210 @@
+use serde::Serialize;
+
+use crate::utils::{Coord, Harbor, Resource};
+use crate::state::PlayerId;
+use crate::board::utils::topology::Topology;
+use crate::board::analysis::{StaticBoard, IntersectionValue, intersection_value};
+use crate::board::reach::{DynamicBoard, settlement_site};
+
+/// Weights used to rank the initial placements
+#[derive(Copy, Clone, PartialEq, Debug, Serialize)]
+pub struct OpeningWeights {
+    /// Per pip of the settlement
+    pub pips: f32,
+    /// Per resource produced by the settlement
+    pub diversity: f32,
+    /// Per resource the player's other settlements don't produce
+    pub new_resources: f32,
+    /// Per harbor, special ones scaled by the pips of their resource
+    pub harbor: f32,
+    /// Share of the second settlement expected to be left after the opponents' replies
+    pub lookahead: f32,
+    /// Share of the best site the road leads to
+    pub road: f32,
+}
+
+impl Default for OpeningWeights {
+    fn default() -> Self {
+        OpeningWeights {
+            pips: 1.0,
+            diversity: 1.5,
+            new_resources: 1.0,
+            harbor: 1.0,
+            lookahead: 0.7,
+            road: 0.2,
+        }
+    }
+}
+
+/// Settlement and road placed together during the initial placement
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct OpeningCandidate {
+    pub settlement: Coord,
+    pub road: Coord,
+    pub value: f32,
+    /// Production of the settlement
+    pub site: IntersectionValue,
+    /// Second settlement expected to be left after the opponents' replies, for first placements
+    pub follow_up: Option<Coord>,
+}
+
+/// Settlement sites still free once some sites are taken
+struct Sites {
+    taken: Vec<Coord>,
+}
+
+impl Sites {
+    fn free<B : DynamicBoard + ?Sized>(&self, board: &B, intersection: Coord) -> bool {
+        settlement_site(board, intersection)
+            && !self.taken.contains(&intersection)
+            && board.board_layout().intersection_intersection_neighbours(intersection)
+                .map(|neighbours| neighbours.iter().all(|neighbour| !self.taken.contains(neighbour)))
+                .unwrap_or(false)
+    }
+}
+
+/// Ranks every legal settlement and road pair for the next initial placement of the player, best first
+///
+/// Seats follow the player ids. For a first placement, the opponents placing before the
+/// player's second settlement are assumed to take the most productive sites left, and
+/// the best site remaining afterward adds to the value of the candidate
+pub fn rank_openings<B>(board: &B, player: PlayerId, player_count: u8, weights: &OpeningWeights) -> Vec<OpeningCandidate>
+where B : StaticBoard + DynamicBoard + ?Sized {
+    let layout = DynamicBoard::board_layout(board);
+    let produced = production(board, player);
+    let placing_second = produced.1 > 0;
+    let replies: Vec<PlayerId> = if placing_second {
+        Vec::new()
+    } else {
+        let next = player.to_u8() + 1..player_count;
+        next.clone().chain(next.rev()).map(PlayerId::from).collect()
+    };
+    let mut candidates = Vec::new();
+    for settlement in layout.intersections.iter().cloned() {
+        if !settlement_site(board, settlement) {
+            continue;
+        }
+        let site = intersection_value(board, settlement);
+        let mut value = site_value(&site, &produced.0, weights);
+        let combined = produced_with(&produced.0, &site);
+        let mut sites = Sites { taken: vec![settlement] };
+        let mut follow_up = None;
+        if !placing_second {
+            for _ in replies.iter() {
+                if let Some(reply) = best_site(board, &sites, |value| weights.pips * value.pips as f32 + weights.diversity * value.diversity as f32) {
+                    sites.taken.push(reply.0);
+                }
+            }
+            if let Some((second, second_value)) = best_site(board, &sites, |value| site_value(value, &combined, weights)) {
+                value += weights.lookahead * second_value;
+                follow_up = Some(second);
+            }
+        }
+        for road in layout.intersection_path_neighbours(settlement).unwrap_or_default() {
+            if !layout.paths.contains(&road) || board.board_path(road).is_some() {
+                continue;
+            }
+            let road_value = road_value(board, &sites, settlement, road, &combined, weights);
+            candidates.push(OpeningCandidate {
+                settlement,
+                road,
+                value: value + weights.road * road_value,
+                site: site.clone(),
+                follow_up,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(std::cmp::Ordering::Equal));
+    candidates
+}
+
+/// Best road for a settlement placed during the initial placement, leading to the best free site
+pub fn opening_road<B>(board: &B, player: PlayerId, settlement: Coord, weights: &OpeningWeights) -> Option<Coord>
+where B : StaticBoard + DynamicBoard + ?Sized {
+    let layout = DynamicBoard::board_layout(board);
+    let produced = production(board, player).0;
+    let sites = Sites { taken: Vec::new() };
+    layout.intersection_path_neighbours(settlement).unwrap_or_default().into_iter()
+        .filter(|road| layout.paths.contains(road) && board.board_path(*road).is_none())
+        .map(|road| (road, road_value(board, &sites, settlement, road, &produced, weights)))
+        .fold(None, |best: Option<(Coord, f32)>, (road, value)| match best {
+            Some((_, best_value)) if best_value >= value => best,
+            _ => Some((road, value)),
+        })
+        .map(|(road, _)| road)
+}
+
+/// Settlement of the player that has no road yet, the one placed last during the initial placement
+pub fn unconnected_settlement<B : DynamicBoard + ?Sized>(board: &B, player: PlayerId) -> Option<Coord> {
+    let layout = board.board_layout();
+    layout.intersections.iter().cloned().find(|intersection| {
+        matches!(board.board_intersection(*intersection), Some((owner, _)) if owner == player)
+            && layout.intersection_path_neighbours(*intersection).unwrap_or_default().iter()
+                .all(|path| board.board_path(*path) != Some(player))
+    })
+}
+
+/// Pips of each resource around the settlements of the player, and the number of settlements
+fn production<B>(board: &B, player: PlayerId) -> ([u8; Resource::COUNT], u8)
+where B : StaticBoard + DynamicBoard + ?Sized {
+    let mut production = [0; Resource::COUNT];
+    let mut count = 0;
+    for intersection in DynamicBoard::board_layout(board).intersections.iter() {
+        if let Some((owner, _)) = board.board_intersection(*intersection) {
+            if owner == player {
+                count += 1;
+                production = produced_with(&production, &intersection_value(board, *intersection));
+            }
+        }
+    }
+    (production, count)
+}
+
+fn produced_with(produced: &[u8; Resource::COUNT], site: &IntersectionValue) -> [u8; Resource::COUNT] {
+    let mut combined = *produced;
+    for (total, pips) in combined.iter_mut().zip(site.production.iter()) {
+        *total += pips;
+    }
+    combined
+}
+
+/// Value of a settlement for a player already producing some resources
+fn site_value(site: &IntersectionValue, produced: &[u8; Resource::COUNT], weights: &OpeningWeights) -> f32 {
+    let new_resources = Resource::ALL.iter()
+        .filter(|resource| site.production[resource.to_usize()] > 0 && produced[resource.to_usize()] == 0)
+        .count() as f32;
+    let harbor = match site.harbor {
+        Harbor::None => 0.0,
+        Harbor::Generic => 1.5,
+        // Only worth it with enough of its resource to trade
+        Harbor::Special(resource) => (produced[resource.to_usize()] + site.production[resource.to_usize()]) as f32 / 2.0,
+    };
+    weights.pips * site.pips as f32
+        + weights.diversity * site.diversity as f32
+        + weights.new_resources * new_resources
+        + weights.harbor * harbor
+}
+
+/// Free site with the highest value, the first one when tied
+fn best_site<B, F>(board: &B, sites: &Sites, value: F) -> Option<(Coord, f32)>
+where B : StaticBoard + DynamicBoard + ?Sized, F : Fn(&IntersectionValue) -> f32 {
+    DynamicBoard::board_layout(board).intersections.iter().cloned()
+        .filter(|intersection| sites.free(board, *intersection))
+        .map(|intersection| (intersection, value(&intersection_value(board, intersection))))
+        .fold(None, |best, (intersection, value)| match best {
+            Some((_, best_value)) if best_value >= value => best,
+            _ => Some((intersection, value)),
+        })
+}
+
+/// Value of the best free site one road after the end of the road
+fn road_value<B>(board: &B, sites: &Sites, settlement: Coord, road: Coord, produced: &[u8; Resource::COUNT], weights: &OpeningWeights) -> f32
+where B : StaticBoard + DynamicBoard + ?Sized {
+    let layout = DynamicBoard::board_layout(board);
+    layout.path_intersection_neighbours(road).unwrap_or_default().into_iter()
+        .filter(|end| *end != settlement && board.board_intersection(*end).is_none())
+        .flat_map(|end| layout.intersection_intersection_neighbours(end).unwrap_or_default())
+        .filter(|site| sites.free(board, *site))
+        .map(|site| site_value(&intersection_value(board, site), produced, weights))
+        .fold(0.0, f32::max)
+}
@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use crate::utils::Coord;
+use crate::state::{StateTrait, PlayerView, PlayerId};
+use crate::board::utils::topology::Topology;
+use crate::board::Layout;
+
+/// Anything with a dynamic board whose road network can be explored
+pub trait DynamicBoard {
+    fn board_layout(&self) -> &Layout;
+
+    fn board_path(&self, coord: Coord) -> Option<PlayerId>;
+
+    fn board_intersection(&self, coord: Coord) -> Option<(PlayerId, bool)>;
+}
+
+impl<T : StateTrait + ?Sized> DynamicBoard for T {
+    fn board_layout(&self) -> &Layout {
+        self.get_layout()
+    }
+
+    fn board_path(&self, coord: Coord) -> Option<PlayerId> {
+        self.get_dynamic_path(coord).unwrap_or(None)
+    }
+
+    fn board_intersection(&self, coord: Coord) -> Option<(PlayerId, bool)> {
+        self.get_dynamic_intersection(coord).unwrap_or(None)
+    }
+}
+
+impl DynamicBoard for PlayerView<'_> {
+    fn board_layout(&self) -> &Layout {
+        self.get_layout()
+    }
+
+    fn board_path(&self, coord: Coord) -> Option<PlayerId> {
+        self.get_dynamic_path(coord).unwrap_or(None)
+    }
+
+    fn board_intersection(&self, coord: Coord) -> Option<(PlayerId, bool)> {
+        self.get_dynamic_intersection(coord).unwrap_or(None)
+    }
+}
+
+/// Is the intersection free for a settlement, following the distance rule
+pub fn settlement_site<B : DynamicBoard + ?Sized>(board: &B, intersection: Coord) -> bool {
+    board.board_intersection(intersection).is_none()
+        && board.board_layout().intersection_intersection_neighbours(intersection)
+            .map(|neighbours| neighbours.iter().all(|neighbour| board.board_intersection(*neighbour).is_none()))
+            .unwrap_or(false)
+}
+
+/// Number of roads a player needs to build to reach each intersection
+///
+/// Roads of the player are free, roads of opponents can't be used, and the network
+/// can reach an opponent's building but not go through it
+pub struct RoadDistances<'a> {
+    player: PlayerId,
+    layout: &'a Layout,
+    distances: Vec<Option<u8>>,
+    /// Path, and position of the intersection, the shortest plan comes from
+    previous: Vec<Option<(Coord, usize)>>,
+}
+
+impl<'a> RoadDistances<'a> {
+    /// Explores from every building and road of the player at once
+    pub fn new<B : DynamicBoard + ?Sized>(board: &'a B, player: PlayerId) -> RoadDistances<'a> {
+        let layout = board.board_layout();
+        let intersections = &layout.intersections;
+        let mut distances = vec![None; intersections.len()];
+        let mut previous = vec![None; intersections.len()];
+        let mut queue = VecDeque::new();
+        for (i, intersection) in intersections.iter().enumerate() {
+            let owned_building = matches!(board.board_intersection(*intersection), Some((p, _)) if p == player);
+            let owned_road = layout.intersection_path_neighbours(*intersection)
+                .map(|paths| paths.iter().any(|path| board.board_path(*path) == Some(player)))
+                .unwrap_or(false);
+            if owned_building || owned_road {
+                distances[i] = Some(0);
+                queue.push_back(i);
+            }
+        }
+        // 0-1 breadth first search: owned roads are pushed in front, new roads at the back
+        let mut done = vec![false; intersections.len()];
+        while let Some(i) = queue.pop_front() {
+            if done[i] {
+                continue;
+            }
+            done[i] = true;
+            let intersection = intersections[i];
+            if matches!(board.board_intersection(intersection), Some((p, _)) if p != player) {
+                continue;
+            }
+            let distance = distances[i].expect("Queued intersections have a distance");
+            for path in layout.intersection_path_neighbours(intersection).unwrap_or_default() {
+                let cost = match board.board_path(path) {
+                    Some(p) if p == player => 0,
+                    Some(_) => continue,
+                    None => 1,
+                };
+                for next in layout.path_intersection_neighbours(path).unwrap_or_default() {
+                    let j = match layout.position(next) {
+                        Some(j) => j,
+                        None => continue,
+                    };
+                    if distances[j].iter().all(|current| distance + cost < *current) {
+                        distances[j] = Some(distance + cost);
+                        previous[j] = Some((path, i));
+                        if cost == 0 {
+                            queue.push_front(j);
+                        } else {
+                            queue.push_back(j);
+                        }
+                    }
+                }
+            }
+        }
+        RoadDistances {
+            player,
+            layout,
+            distances,
+            previous,
+        }
+    }
+
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    /// Roads to build to reach the intersection, `None` if it can't be reached
+    pub fn distance(&self, intersection: Coord) -> Option<u8> {
+        self.distances[self.layout.position(intersection)?]
+    }
+
+    /// Paths where roads have to be built to reach the intersection, starting from the network
+    pub fn road_plan(&self, intersection: Coord) -> Option<Vec<Coord>> {
+        let mut i = self.layout.position(intersection)?;
+        self.distances[i]?;
+        let mut plan = Vec::new();
+        while let Some((path, from)) = self.previous[i] {
+            if self.distances[from] != self.distances[i] {
+                plan.push(path);
+            }
+            i = from;
+        }
+        plan.reverse();
+        Some(plan)
+    }
+
+    /// Every reachable intersection with its distance
+    pub fn reachable(&self) -> Vec<(Coord, u8)> {
+        self.layout.intersections.iter().zip(self.distances.iter())
+            .filter_map(|(intersection, distance)| Some((*intersection, (*distance)?)))
+            .collect()
+    }
+
+    /// Legal settlement sites the player can reach, closest first
+    pub fn settlement_sites<B : DynamicBoard + ?Sized>(&self, board: &B) -> Vec<(Coord, u8)> {
+        let mut sites: Vec<(Coord, u8)> = self.reachable().into_iter()
+            .filter(|(intersection, _)| settlement_site(board, *intersection))
+            .collect();
+        sites.sort_by_key(|(_, distance)| *distance);
+        sites
+    }
+
+    /// Legal settlement sites the player can't reach, because opponents cut them off
+    ///
+    /// Empty while the player has nothing on the board
+    pub fn cut_off_sites<B : DynamicBoard + ?Sized>(&self, board: &B) -> Vec<Coord> {
+        if self.distances.iter().all(Option::is_none) {
+            return Vec::new();
+        }
+        self.layout.intersections.iter().zip(self.distances.iter())
+            .filter(|(intersection, distance)| distance.is_none() && settlement_site(board, **intersection))
+            .map(|(intersection, _)| *intersection)
+            .collect()
+    }
+}
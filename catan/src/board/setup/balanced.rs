@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::state::{State, StateMaker, StateTrait};
+use crate::board::layout::{Layout, LayoutSpec, SpecError};
+use crate::board::utils::topology::Topology;
+use crate::board::analysis::{analyse, BoardReport};
+use crate::utils::{Coord, Hex, LandHex, Resource, DevelopmentCards, DevelopmentDeck};
+use super::random_spec::{place_tiles, place_harbors};
+
+/// Constraints a balanced board has to respect
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BalanceConstraints {
+    /// Allows 6 and 8 tokens to be next to each other
+    pub adjacent_red_numbers: bool,
+    /// Maximum number of neighbours of the same resource a hex can have
+    pub max_same_resource_adjacency: Option<u8>,
+    /// Maximum difference of average pips per tile between two resources
+    pub max_pip_spread: Option<f32>,
+    /// Places the number tokens randomly instead of along the track
+    pub random_tokens: bool,
+    /// Number of boards generated before giving up
+    pub max_attempts: u32,
+}
+
+impl BalanceConstraints {
+    /// Any board is accepted
+    pub const NONE: BalanceConstraints = BalanceConstraints {
+        adjacent_red_numbers: true,
+        max_same_resource_adjacency: None,
+        max_pip_spread: None,
+        random_tokens: false,
+        max_attempts: 1,
+    };
+}
+
+impl Default for BalanceConstraints {
+    fn default() -> Self {
+        BalanceConstraints {
+            adjacent_red_numbers: false,
+            max_same_resource_adjacency: Some(1),
+            max_pip_spread: Some(1.5),
+            random_tokens: true,
+            max_attempts: 10_000,
+        }
+    }
+}
+
+/// Measures of how fair a board is
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BoardMetrics {
+    /// Pairs of neighbour hexes both holding a 6 or an 8
+    pub adjacent_red_pairs: u8,
+    /// Largest number of neighbours of the same resource of any hex
+    pub max_same_resource_adjacency: u8,
+    /// Total pips of each resource
+    pub pips: [u8; Resource::COUNT],
+    /// Difference between the highest and lowest average pips per tile of the resources
+    pub pip_spread: f32,
+}
+
+impl BoardMetrics {
+    pub fn of(state: &dyn StateTrait) -> BoardMetrics {
+        let mut adjacent_red_pairs = 0;
+        let mut max_same_resource_adjacency = 0;
+        let BoardReport { pips, tiles, .. } = analyse(state);
+        let hexes = &state.get_layout().hexes;
+        for hex in hexes.iter() {
+            let tile = state.get_static_hex(*hex).unwrap_or(Hex::Water);
+            let resource = resource(tile);
+            let mut same_resource = 0;
+            for neighbour in Coord::TOPOLOGY.hex_hex_neighbours(*hex).unwrap_or_default() {
+                if !hexes.contains(&neighbour) {
+                    continue;
+                }
+                let neighbour_tile = state.get_static_hex(neighbour).unwrap_or(Hex::Water);
+                if resource.is_some() && resource == self::resource(neighbour_tile) {
+                    same_resource += 1;
+                }
+                // Each pair is seen from both sides
+                if *hex < neighbour && is_red(tile) && is_red(neighbour_tile) {
+                    adjacent_red_pairs += 1;
+                }
+            }
+            max_same_resource_adjacency = max_same_resource_adjacency.max(same_resource);
+        }
+        let averages: Vec<f32> = Resource::ALL.iter()
+            .filter(|resource| tiles[resource.to_usize()] > 0)
+            .map(|resource| pips[resource.to_usize()] as f32 / tiles[resource.to_usize()] as f32)
+            .collect();
+        let highest = averages.iter().cloned().fold(f32::MIN, f32::max);
+        let lowest = averages.iter().cloned().fold(f32::MAX, f32::min);
+        BoardMetrics {
+            adjacent_red_pairs,
+            max_same_resource_adjacency,
+            pips,
+            pip_spread: if averages.is_empty() { 0.0 } else { highest - lowest },
+        }
+    }
+
+    pub fn satisfies(&self, constraints: &BalanceConstraints) -> bool {
+        (constraints.adjacent_red_numbers || self.adjacent_red_pairs == 0)
+            && constraints.max_same_resource_adjacency.iter().all(|max| self.max_same_resource_adjacency <= *max)
+            && constraints.max_pip_spread.iter().all(|max| self.pip_spread <= *max)
+    }
+}
+
+fn resource(hex: Hex) -> Option<Resource> {
+    match hex {
+        Hex::Land(LandHex::Prod(resource, _)) => Some(resource),
+        _ => None,
+    }
+}
+
+fn is_red(hex: Hex) -> bool {
+    hex.pips() == 5
+}
+
+/// Reasons a balanced board couldn't be generated
+#[derive(Clone, PartialEq, Debug)]
+pub enum BalanceError {
+    Spec(SpecError),
+    /// No board respected the constraints, the metrics of the last one are given
+    Unsatisfied(BoardMetrics),
+}
+
+impl From<SpecError> for BalanceError {
+    fn from(error: SpecError) -> BalanceError {
+        BalanceError::Spec(error)
+    }
+}
+
+pub fn balanced_setup<T : StateMaker, R : Rng>(rng: &mut R, spec: &LayoutSpec, layout: Arc<Layout>, player_count: u8, constraints: &BalanceConstraints) -> Result<(State, BoardMetrics), BalanceError> {
+    let mut state = T::new_empty(layout, player_count);
+    let metrics = balanced_setup_existing_state(rng, spec, state.as_mut(), constraints)?;
+    Ok((state, metrics))
+}
+
+/// Generates boards from a layout specification until one respects the constraints
+///
+/// Returns the metrics of the accepted board
+pub fn balanced_setup_existing_state<R : Rng>(rng: &mut R, spec: &LayoutSpec, state: &mut dyn StateTrait, constraints: &BalanceConstraints) -> Result<BoardMetrics, BalanceError> {
+    spec.validate()?;
+    let mut metrics = None;
+    for _ in 0..constraints.max_attempts.max(1) {
+        if constraints.random_tokens {
+            place_random_tokens(rng, spec, state)?;
+        } else {
+            place_tiles(rng, spec, state)?;
+        }
+        let current = BoardMetrics::of(state);
+        metrics = Some(current);
+        if current.satisfies(constraints) {
+            place_harbors(rng, spec, state)?;
+            *state.get_development_deck_mut() = DevelopmentDeck::shuffled(DevelopmentCards::STARTING_DECK, rng);
+            return Ok(current);
+        }
+    }
+    Err(BalanceError::Unsatisfied(metrics.unwrap()))
+}
+
+/// Shuffles the land tiles and the number tokens independently
+fn place_random_tokens<R : Rng>(rng: &mut R, spec: &LayoutSpec, state: &mut dyn StateTrait) -> Result<(), SpecError> {
+    let mut landtiles = spec.tiles.clone();
+    landtiles.shuffle(rng);
+    let mut tokens = spec.tokens.clone();
+    tokens.shuffle(rng);
+    let mut tokens = tokens.into_iter();
+    for (coord, landtile) in spec.hex_coords().into_iter().zip(landtiles) {
+        let token = if landtile.resource().is_some() { tokens.next().unwrap() } else { 0 };
+        state.set_static_hex(coord, landtile.hex(token))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use once_cell::sync::Lazy;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+
+use crate::state::{State, StateMaker, StateTrait, PlayerId};
+use crate::board::layout::{self, Layout, LayoutSpec, SpecError};
+use crate::board::utils::topology::Topology;
+use crate::game::{Scenario, Phase, Violation};
+use crate::utils::{Coord, Hex, LandHex, Harbor, Resources, DevelopmentCards, DevelopmentDeck};
+
+/// Settlement and road placed before the game starts
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct StartingPlacement {
+    pub player: u8,
+    pub settlement: [i8; 2],
+    pub road: [i8; 2],
+}
+
+/// Board whose tiles, tokens and harbors aren't shuffled
+///
+/// Tiles are placed in order along the track, and harbor tiles in order over the harbors.
+/// Starting placements are optional, and given in the order they are played
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FixedBoard {
+    pub layout: LayoutSpec,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub starting: Vec<StartingPlacement>,
+}
+
+impl FixedBoard {
+    pub fn from_toml(text: &str) -> Result<FixedBoard, SpecError> {
+        toml::from_str(text).map_err(|error| SpecError::Parse(error.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<FixedBoard, SpecError> {
+        serde_json::from_str(text).map_err(|error| SpecError::Parse(error.to_string()))
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("Failed writing board")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed writing board")
+    }
+
+    /// Builds a state with this board, and nothing placed on it
+    ///
+    /// Boards covering the same hexes as the default layout share it, whatever order they list them in
+    pub fn setup<T : StateMaker, R : Rng>(&self, rng: &mut R, player_count: u8) -> Result<State, SpecError> {
+        let mut hexes = self.layout.hex_coords();
+        let mut default = layout::DEFAULT.hexes.clone();
+        hexes.sort();
+        default.sort();
+        let shared = if hexes == default {
+            layout::DEFAULT.clone()
+        } else {
+            Arc::new(self.layout.layout()?)
+        };
+        fixed_setup::<T, R>(rng, self, shared, player_count)
+    }
+
+    /// Builds a state with this board and its starting placements, ready for the first turn
+    ///
+    /// Placements of players that aren't in the game are skipped.
+    /// Players receive the resources around their last settlement, like at the end of the initial placement
+    pub fn starting_position<T : StateMaker, R : Rng>(&self, rng: &mut R, player_count: u8) -> Result<(State, Phase), FixedBoardError> {
+        let state = self.setup::<T, R>(rng, player_count).map_err(FixedBoardError::Spec)?;
+        let placements: Vec<&StartingPlacement> = self.starting.iter()
+            .filter(|placement| placement.player < player_count)
+            .collect();
+        let mut resources = vec![Resources::ZERO; player_count as usize];
+        for (i, placement) in placements.iter().enumerate() {
+            if placements[i+1..].iter().any(|later| later.player == placement.player) {
+                continue;
+            }
+            let [x, y] = placement.settlement;
+            let hexes = Coord::TOPOLOGY.intersection_hex_neighbours(Coord::new(x, y))
+                .map_err(|error| FixedBoardError::Spec(SpecError::Coord(error)))?;
+            for hex in hexes {
+                if let Ok(Hex::Land(LandHex::Prod(resource, _))) = state.get_static_hex(hex) {
+                    resources[placement.player as usize][resource] += 1;
+                }
+            }
+        }
+        let mut scenario = Scenario::from_state(state);
+        for placement in placements {
+            let player = PlayerId::from(placement.player);
+            let [x, y] = placement.settlement;
+            let [road_x, road_y] = placement.road;
+            scenario = scenario
+                .settlement(player, Coord::new(x, y))
+                .road(player, Coord::new(road_x, road_y));
+        }
+        for (player, resources) in resources.into_iter().enumerate() {
+            scenario = scenario.resources(PlayerId::from(player), resources);
+        }
+        scenario.phase(Phase::START_TURNS).build().map_err(FixedBoardError::Violations)
+    }
+}
+
+/// Reasons a fixed board's starting position can't be built
+#[derive(Clone, PartialEq, Debug)]
+pub enum FixedBoardError {
+    /// The board itself is invalid
+    Spec(SpecError),
+    /// The starting placements aren't a coherent position
+    Violations(Vec<Violation>),
+}
+
+pub fn fixed_setup<T : StateMaker, R : Rng>(rng: &mut R, board: &FixedBoard, layout: Arc<Layout>, player_count: u8) -> Result<State, SpecError> {
+    let mut state = T::new_empty(layout, player_count);
+    fixed_setup_existing_state(rng, board, state.as_mut())?;
+    Ok(state)
+}
+
+/// Places the tiles, tokens and harbors of a fixed board on a state
+///
+/// Development cards are still shuffled
+pub fn fixed_setup_existing_state<R : Rng>(rng: &mut R, board: &FixedBoard, state: &mut dyn StateTrait) -> Result<(), SpecError> {
+    let spec = &board.layout;
+    spec.validate()?;
+    let mut tokens = spec.tokens.iter();
+    for (coord, landtile) in spec.track_coords().into_iter().zip(spec.tiles.iter()) {
+        let token = if landtile.resource().is_some() { *tokens.next().unwrap() } else { 0 };
+        state.set_static_hex(coord, landtile.hex(token))?;
+    }
+    for intersection_coord in state.get_layout().intersections.clone() {
+        state.set_static_harbor(intersection_coord, Harbor::None)?;
+    }
+    for (path_coord, porttile) in spec.harbor_coords().into_iter().zip(spec.harbor_tiles.iter()) {
+        for intersection_coord in Coord::TOPOLOGY.path_intersection_neighbours(path_coord)? {
+            state.set_static_harbor(intersection_coord, porttile.harbor())?;
+        }
+    }
+    *state.get_development_deck_mut() = DevelopmentDeck::shuffled(DevelopmentCards::STARTING_DECK, rng);
+    Ok(())
+}
+
+/// Beginner board, with the suggested starting placements
+pub static BEGINNER: Lazy<FixedBoard> = Lazy::new(||
+    FixedBoard::from_toml(include_str!("../../../data/beginner.toml")).expect("Failed reading beginner board")
+);
+
+static FIXED_BOARDS: Lazy<RwLock<HashMap<String, Arc<FixedBoard>>>> = Lazy::new(|| {
+    let mut boards = HashMap::new();
+    boards.insert(String::from("beginner"), Arc::new(BEGINNER.clone()));
+    RwLock::new(boards)
+});
+
+/// Makes a fixed board available by name, replacing any board with the same name
+pub fn register_fixed_board(name: &str, board: FixedBoard) -> Result<(), SpecError> {
+    board.layout.validate()?;
+    FIXED_BOARDS.write().unwrap().insert(String::from(name), Arc::new(board));
+    Ok(())
+}
+
+pub fn fixed_board(name: &str) -> Option<Arc<FixedBoard>> {
+    FIXED_BOARDS.read().unwrap().get(name).cloned()
+}
+
+/// Names of the registered fixed boards, sorted
+pub fn fixed_board_names() -> Vec<String> {
+    let mut names: Vec<String> = FIXED_BOARDS.read().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+pub fn beginner_setup<T : StateMaker, R : Rng>(rng: &mut R, player_count: u8) -> State {
+    BEGINNER.setup::<T, R>(rng, player_count).expect("Failed setting beginner board")
+}
@@ -1,9 +1,11 @@
 mod random_default;
+mod random_spec;
+mod balanced;
+mod fixed;
 
-use super::Coord;
 pub use random_default::random_default_setup_existing_state;
 pub use random_default::random_default_setup as random_default;
-
-const fn c(y:i8, x:i8) -> Coord {
-    Coord::new(x,y)
-}
+pub use random_spec::{random_spec_setup, random_spec_setup_existing_state, random_island_setup};
+pub use balanced::{balanced_setup, balanced_setup_existing_state, BalanceConstraints, BalanceError, BoardMetrics};
+pub use fixed::{fixed_setup, fixed_setup_existing_state, beginner_setup, FixedBoard, FixedBoardError, StartingPlacement, BEGINNER};
+pub use fixed::{register_fixed_board, fixed_board, fixed_board_names};
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::state::{State, StateMaker, StateTrait};
+use crate::board::layout::{Layout, LayoutSpec, SpecError, IslandParams, IslandError, random_island_spec, is_coastal};
+use crate::board::utils::{Coord, CoordTransform};
+use crate::board::utils::topology::Topology;
+use crate::utils::{Hex, LandHex, Harbor, DevelopmentCards, DevelopmentDeck};
+
+pub fn random_spec_setup<T : StateMaker, R : Rng>(rng: &mut R, spec: &LayoutSpec, layout: Arc<Layout>, player_count: u8) -> Result<State, SpecError> {
+    let mut state = T::new_empty(layout, player_count);
+    random_spec_setup_existing_state(rng, spec, state.as_mut())?;
+    Ok(state)
+}
+
+/// Generates a random landmass, and sets it up like any layout specification
+///
+/// The center of the board can be water, so the thief starts on a desert
+pub fn random_island_setup<T : StateMaker, R : Rng>(rng: &mut R, params: &IslandParams, player_count: u8) -> Result<State, IslandError> {
+    let spec = random_island_spec(rng, params)?;
+    let layout = Arc::new(spec.layout()?);
+    let mut state = random_spec_setup::<T, R>(rng, &spec, layout, player_count)?;
+    let desert = state.get_layout().hexes.iter().cloned()
+        .find(|hex| state.get_static_hex(*hex) == Ok(Hex::Land(LandHex::Desert)));
+    if let Some(desert) = desert {
+        state.set_thief_hex(desert);
+    }
+    Ok(state)
+}
+
+/// Shuffles the pools of a layout specification over a state
+///
+/// The whole board is randomly rotated or flipped when the layout allows it
+pub fn random_spec_setup_existing_state<R : Rng>(rng: &mut R, spec: &LayoutSpec, state: &mut dyn StateTrait) -> Result<(), SpecError> {
+    spec.validate()?;
+    place_tiles(rng, spec, state)?;
+    place_harbors(rng, spec, state)?;
+    // development cards
+    *state.get_development_deck_mut() = DevelopmentDeck::shuffled(DevelopmentCards::STARTING_DECK, rng);
+    Ok(())
+}
+
+/// Shuffles the land tiles, and places the number tokens along the track
+pub(super) fn place_tiles<R : Rng>(rng: &mut R, spec: &LayoutSpec, state: &mut dyn StateTrait) -> Result<(), SpecError> {
+    let hexes = spec.hex_coords();
+    let mut landtiles = spec.tiles.clone();
+    landtiles.shuffle(rng);
+    let mut transform = CoordTransform::random(Coord::ZERO, rng);
+    if !spec.track_coords().iter().all(|coord| hexes.contains(&transform.transform(*coord))) {
+        transform = CoordTransform::new(Coord::ZERO, 0, false);
+    }
+    let mut tokens = spec.tokens.iter();
+    for (coord, landtile) in spec.track_coords().into_iter().zip(landtiles) {
+        let token = if landtile.resource().is_some() { *tokens.next().unwrap() } else { 0 };
+        state.set_static_hex(transform.transform(coord), landtile.hex(token))?;
+    }
+    Ok(())
+}
+
+pub(super) fn place_harbors<R : Rng>(rng: &mut R, spec: &LayoutSpec, state: &mut dyn StateTrait) -> Result<(), SpecError> {
+    let hexes = spec.hex_coords();
+    let mut porttiles = spec.harbor_tiles.clone();
+    porttiles.shuffle(rng);
+    let mut transform = CoordTransform::new(
+        Coord::ZERO,
+        if rng.gen() {0} else {3},
+        false
+    );
+    for coord in spec.harbor_coords() {
+        if !is_coastal(&hexes, transform.transform(coord))? {
+            transform = CoordTransform::new(Coord::ZERO, 0, false);
+            break;
+        }
+    }
+    for intersection_coord in state.get_layout().intersections.clone() {
+        state.set_static_harbor(intersection_coord, Harbor::None)?;
+    }
+    for (path_coord, porttile) in spec.harbor_coords().into_iter().zip(porttiles) {
+        for intersection_coord in Coord::TOPOLOGY.path_intersection_neighbours(transform.transform(path_coord))? {
+            state.set_static_harbor(intersection_coord, porttile.harbor())?;
+        }
+    }
+    Ok(())
+}
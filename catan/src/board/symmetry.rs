@@ -0,0 +1,164 @@
+use crate::utils::{Coord, Hex, Harbor};
+use crate::state::{State, StateTrait, PlayerId};
+use crate::game::{Action, Phase, write_notation};
+use crate::board::{Layout, Error};
+use crate::board::utils::CoordTransform;
+
+type Intersection = (Coord, Harbor, Option<(PlayerId, bool)>);
+
+/// One of the 12 rotations and reflections of a hexagonal board around its center
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Symmetry {
+    /// Number of sixths of turn
+    pub rotation: u8,
+    pub flip: bool,
+}
+
+impl Symmetry {
+    pub const COUNT: usize = 12;
+    pub const IDENTITY: Symmetry = Symmetry { rotation: 0, flip: false };
+
+    /// Every symmetry, starting with the [IDENTITY](Symmetry::IDENTITY)
+    pub fn all() -> Vec<Symmetry> {
+        (0..Symmetry::COUNT).map(Symmetry::from_index).collect()
+    }
+
+    /// Inverse of [index](Symmetry::index)
+    pub fn from_index(index: usize) -> Symmetry {
+        Symmetry {
+            rotation: (index % 6) as u8,
+            flip: index >= 6,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self.rotation as usize + if self.flip { 6 } else { 0 }
+    }
+
+    fn transform(self) -> CoordTransform {
+        CoordTransform::new(Coord::ZERO, self.rotation as i32, self.flip)
+    }
+
+    /// Image of a coord, which can't be void
+    pub fn coord(self, coord: Coord) -> Coord {
+        self.transform().transform(coord)
+    }
+
+    /// The symmetry that undoes this one
+    pub fn inverse(self) -> Symmetry {
+        // Two coords that aren't on a common axis of symmetry are enough to tell symmetries apart
+        let probes = [Coord::new(4, 0), Coord::new(10, 2)];
+        Symmetry::all().into_iter()
+            .find(|inverse| probes.iter().all(|probe| inverse.coord(self.coord(*probe)) == *probe))
+            .expect("Every symmetry has an inverse")
+    }
+
+    /// Are the hexes of the layout sent on hexes of the layout
+    pub fn preserves(self, layout: &Layout) -> bool {
+        layout.hexes.iter().all(|hex| layout.hexes.contains(&self.coord(*hex)))
+    }
+
+    /// Symmetries that send the layout onto itself, starting with the [IDENTITY](Symmetry::IDENTITY)
+    pub fn preserving(layout: &Layout) -> Vec<Symmetry> {
+        Symmetry::all().into_iter().filter(|symmetry| symmetry.preserves(layout)).collect()
+    }
+
+    /// Image of an action, moving the coords it targets
+    pub fn action(self, action: Action) -> Action {
+        match action {
+            Action::MoveThief { hex, victim } => Action::MoveThief { hex: self.coord(hex), victim },
+            Action::BuildRoad { path } => Action::BuildRoad { path: self.coord(path) },
+            Action::BuildSettlement { intersection } => Action::BuildSettlement { intersection: self.coord(intersection) },
+            Action::BuildCity { intersection } => Action::BuildCity { intersection: self.coord(intersection) },
+            action => action,
+        }
+    }
+
+    /// Index in `actions` of the image of each action, or `None` if an image is missing
+    ///
+    /// Worth computing once per symmetry when transforming many indices or masks
+    pub fn action_permutation(self, actions: &[Action]) -> Option<Vec<usize>> {
+        actions.iter()
+            .map(|action| {
+                let image = self.action(*action);
+                actions.iter().position(|other| *other == image)
+            })
+            .collect()
+    }
+
+    /// Index of the image of the action at `index`, or `None` if there is no such action or image
+    pub fn action_index(self, actions: &[Action], index: usize) -> Option<usize> {
+        let image = self.action(*actions.get(index)?);
+        actions.iter().position(|other| *other == image)
+    }
+
+    /// Image of a legal-action mask indexed like `actions`
+    pub fn action_mask(self, actions: &[Action], mask: &[bool]) -> Option<Vec<bool>> {
+        Some(permute(&self.action_permutation(actions)?, mask))
+    }
+
+    /// Moves everything on the board of a state: hexes, harbors, thief, roads and buildings
+    ///
+    /// Nothing is changed if the layout isn't preserved
+    pub fn apply_to_state(self, state: &mut dyn StateTrait) -> Result<(), Error> {
+        let layout = state.get_layout();
+        if let Some(hex) = layout.hexes.iter().find(|hex| !layout.hexes.contains(&self.coord(**hex))) {
+            return Err(Error::InvalidCoord(self.coord(*hex)));
+        }
+        let hexes: Vec<(Coord, Hex)> = layout.hexes.iter()
+            .map(|hex| Ok((self.coord(*hex), state.get_static_hex(*hex)?)))
+            .collect::<Result<_, Error>>()?;
+        let paths: Vec<(Coord, Option<PlayerId>)> = layout.paths.iter()
+            .map(|path| Ok((self.coord(*path), state.get_dynamic_path(*path)?)))
+            .collect::<Result<_, Error>>()?;
+        let intersections: Vec<Intersection> = layout.intersections.iter()
+            .map(|intersection| Ok((
+                self.coord(*intersection),
+                state.get_static_harbor(*intersection)?,
+                state.get_dynamic_intersection(*intersection)?,
+            )))
+            .collect::<Result<_, Error>>()?;
+        let thief = self.coord(state.get_thief_hex());
+        for (hex, value) in hexes {
+            state.set_static_hex(hex, value)?;
+        }
+        for (path, owner) in paths {
+            state.set_dynamic_path(path, owner.unwrap_or(PlayerId::NONE))?;
+        }
+        for (intersection, harbor, building) in intersections {
+            state.set_static_harbor(intersection, harbor)?;
+            let (owner, is_city) = building.unwrap_or((PlayerId::NONE, false));
+            state.set_dynamic_intersection(intersection, owner, is_city)?;
+        }
+        state.set_thief_hex(thief);
+        Ok(())
+    }
+}
+
+/// Moves each value to the index given by a [permutation](Symmetry::action_permutation)
+pub fn permute<T: Copy + Default>(permutation: &[usize], values: &[T]) -> Vec<T> {
+    let mut result = vec![T::default(); values.len()];
+    for (value, index) in values.iter().zip(permutation.iter()) {
+        result[*index] = *value;
+    }
+    result
+}
+
+/// Transforms a state into a representative shared by all its symmetric positions
+///
+/// Among the symmetries preserving the layout, picks the one giving the smallest
+/// [notation](crate::game::to_notation), and returns it so that actions can be mapped too
+pub fn canonicalize(state: &mut State, phase: &Phase) -> Symmetry {
+    let mut best = (write_notation(state, phase), Symmetry::IDENTITY);
+    for symmetry in Symmetry::preserving(state.get_layout()).into_iter().skip(1) {
+        symmetry.apply_to_state(state.as_mut()).expect("Symmetry should preserve the layout");
+        let notation = write_notation(state, phase);
+        if notation < best.0 {
+            best = (notation, symmetry);
+        }
+        symmetry.inverse().apply_to_state(state.as_mut()).expect("Symmetry should preserve the layout");
+    }
+    let symmetry = best.1;
+    symmetry.apply_to_state(state.as_mut()).expect("Symmetry should preserve the layout");
+    symmetry
+}
@@ -0,0 +1,285 @@
+use std::ops::{Add, Sub};
+use serde::{Serialize, Deserialize};
+
+use super::{Coord, DetailedCoordType};
+
+/// Direction from a hex to one of its neighbours, and the edge they share
+///
+/// Ordered counterclockwise from the east, like the neighbours given by the [topology](super::topology)
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+/// Corner of a hex
+///
+/// Ordered counterclockwise from the north east, like the intersections given by the [topology](super::topology)
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum HexCorner {
+    NorthEast,
+    North,
+    NorthWest,
+    SouthWest,
+    South,
+    SouthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    pub fn opposite(self) -> HexDirection {
+        HexDirection::ALL[(self as usize + 3) % 6]
+    }
+
+    /// Offset to the neighbouring hex
+    pub fn axial(self) -> Axial {
+        match self {
+            HexDirection::East => Axial::new(1, 0),
+            HexDirection::NorthEast => Axial::new(1, -1),
+            HexDirection::NorthWest => Axial::new(0, -1),
+            HexDirection::West => Axial::new(-1, 0),
+            HexDirection::SouthWest => Axial::new(-1, 1),
+            HexDirection::SouthEast => Axial::new(0, 1),
+        }
+    }
+
+    /// Offset from the center of a hex to the edge
+    fn offset(self) -> Coord {
+        match self {
+            HexDirection::East => Coord::new(2, 0),
+            HexDirection::NorthEast => Coord::new(1, 1),
+            HexDirection::NorthWest => Coord::new(-1, 1),
+            HexDirection::West => Coord::new(-2, 0),
+            HexDirection::SouthWest => Coord::new(-1, -1),
+            HexDirection::SouthEast => Coord::new(1, -1),
+        }
+    }
+}
+
+impl HexCorner {
+    pub const ALL: [HexCorner; 6] = [
+        HexCorner::NorthEast,
+        HexCorner::North,
+        HexCorner::NorthWest,
+        HexCorner::SouthWest,
+        HexCorner::South,
+        HexCorner::SouthEast,
+    ];
+
+    /// Offset from the center of a hex to the corner
+    fn offset(self) -> Coord {
+        match self {
+            HexCorner::NorthEast => Coord::new(2, 1),
+            HexCorner::North => Coord::new(0, 1),
+            HexCorner::NorthWest => Coord::new(-2, 1),
+            HexCorner::SouthWest => Coord::new(-2, -1),
+            HexCorner::South => Coord::new(0, -1),
+            HexCorner::SouthEast => Coord::new(2, -1),
+        }
+    }
+}
+
+/// Axial coordinates of a hex
+///
+/// `q` increases to the east and `r` to the south east, with `(0, 0)` the center of the board.
+/// The third cube coordinate is `s = -q - r`
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Axial {
+    pub q: i8,
+    pub r: i8,
+}
+
+impl Add for Axial {
+    type Output = Axial;
+
+    fn add(self, other: Axial) -> Axial {
+        Axial::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Axial {
+    type Output = Axial;
+
+    fn sub(self, other: Axial) -> Axial {
+        Axial::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Axial {
+    pub const ZERO: Axial = Axial::new(0, 0);
+
+    pub const fn new(q: i8, r: i8) -> Axial {
+        Axial { q, r }
+    }
+
+    /// Axial coordinates of a hex coord, `None` for other coord types
+    pub fn from_coord(coord: Coord) -> Option<Axial> {
+        match coord.get_detailed_type() {
+            DetailedCoordType::OHex => Some(Axial::new((coord.x + coord.y) / 4, -coord.y / 2)),
+            _ => None,
+        }
+    }
+
+    pub fn to_coord(self) -> Coord {
+        Coord::new(4 * self.q + 2 * self.r, -2 * self.r)
+    }
+
+    pub fn s(self) -> i8 {
+        -self.q - self.r
+    }
+
+    /// Cube coordinates `(q, r, s)`
+    pub fn cube(self) -> (i8, i8, i8) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn neighbour(self, direction: HexDirection) -> Axial {
+        self + direction.axial()
+    }
+
+    /// Number of steps between two hexes
+    pub fn distance(self, other: Axial) -> u8 {
+        let (q, r, s) = (other - self).cube();
+        q.unsigned_abs().max(r.unsigned_abs()).max(s.unsigned_abs())
+    }
+
+    /// Hexes at exactly `radius` steps, counterclockwise starting from the east
+    pub fn ring(self, radius: u8) -> Vec<Axial> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut hexes = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self;
+        for _ in 0..radius {
+            hex = hex.neighbour(HexDirection::East);
+        }
+        for direction in HexDirection::ALL.iter() {
+            // Walks along the side that is two directions further
+            let side = HexDirection::ALL[(*direction as usize + 2) % 6];
+            for _ in 0..radius {
+                hexes.push(hex);
+                hex = hex.neighbour(side);
+            }
+        }
+        hexes
+    }
+
+    /// Hexes at most `radius` steps away, ring by ring
+    pub fn spiral(self, radius: u8) -> Vec<Axial> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    /// Hexes crossed by the straight line to another hex, both included
+    pub fn line(self, other: Axial) -> Vec<Axial> {
+        let steps = self.distance(other);
+        if steps == 0 {
+            return vec![self];
+        }
+        // Nudged so that lines along edges are broken consistently
+        let (q0, r0) = (self.q as f32 + 1e-3, self.r as f32 + 1e-3);
+        let (q1, r1) = (other.q as f32 + 1e-3, other.r as f32 + 1e-3);
+        (0..=steps).map(|i| {
+            let t = i as f32 / steps as f32;
+            cube_round(q0 + (q1 - q0) * t, r0 + (r1 - r0) * t)
+        }).collect()
+    }
+}
+
+fn cube_round(q: f32, r: f32) -> Axial {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    Axial::new(rq as i8, rr as i8)
+}
+
+/// Any non void coord, written as a hex with an optional edge or corner
+///
+/// Edges and corners are shared by several hexes, so [from_coord](AxialCoord::from_coord) picks
+/// a canonical one: paths are the east, north east or north west edge of a hex, and
+/// intersections the north or south corner of a hex
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum AxialCoord {
+    Hex(Axial),
+    Edge(Axial, HexDirection),
+    Corner(Axial, HexCorner),
+}
+
+impl AxialCoord {
+    /// `None` for void coords
+    pub fn from_coord(coord: Coord) -> Option<AxialCoord> {
+        let hex = |x: i8, y: i8| Axial::from_coord(Coord::new(x, y)).expect("Coord should be a hex");
+        let (x, y) = (coord.x, coord.y);
+        Some(match coord.get_detailed_type() {
+            DetailedCoordType::OHex => AxialCoord::Hex(hex(x, y)),
+            DetailedCoordType::IPath => AxialCoord::Edge(hex(x - 2, y), HexDirection::East),
+            DetailedCoordType::SPath => AxialCoord::Edge(hex(x - 1, y - 1), HexDirection::NorthEast),
+            DetailedCoordType::ZPath => AxialCoord::Edge(hex(x + 1, y - 1), HexDirection::NorthWest),
+            DetailedCoordType::AIntersection => AxialCoord::Corner(hex(x, y - 1), HexCorner::North),
+            DetailedCoordType::VIntersection => AxialCoord::Corner(hex(x, y + 1), HexCorner::South),
+            DetailedCoordType::LVoid | DetailedCoordType::RVoid => return None,
+        })
+    }
+
+    /// Accepts any edge or corner, not only the canonical ones
+    pub fn to_coord(self) -> Coord {
+        let (hex, offset) = match self {
+            AxialCoord::Hex(hex) => (hex, Coord::ZERO),
+            AxialCoord::Edge(hex, direction) => (hex, direction.offset()),
+            AxialCoord::Corner(hex, corner) => (hex, corner.offset()),
+        };
+        let center = hex.to_coord();
+        Coord::new(center.x + offset.x, center.y + offset.y)
+    }
+
+    /// Same coord, written from the point of view of its canonical hex
+    pub fn canonical(self) -> AxialCoord {
+        AxialCoord::from_coord(self.to_coord()).expect("Axial coord can't be void")
+    }
+
+    pub fn hex(self) -> Axial {
+        match self {
+            AxialCoord::Hex(hex) | AxialCoord::Edge(hex, _) | AxialCoord::Corner(hex, _) => hex,
+        }
+    }
+}
+
+impl Coord {
+    /// Axial hex with an optional edge or corner, `None` for void coords
+    pub fn to_axial(self) -> Option<AxialCoord> {
+        AxialCoord::from_coord(self)
+    }
+
+    /// Number of steps between two hexes, `None` if one of the coords isn't a hex
+    pub fn hex_distance(self, other: Coord) -> Option<u8> {
+        Some(Axial::from_coord(self)?.distance(Axial::from_coord(other)?))
+    }
+}
+
+impl From<Axial> for Coord {
+    fn from(hex: Axial) -> Coord {
+        hex.to_coord()
+    }
+}
+
+impl From<AxialCoord> for Coord {
+    fn from(coord: AxialCoord) -> Coord {
+        coord.to_coord()
+    }
+}
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt;
+use serde::{Serialize, Deserialize};
 
 use super::topology::CoordTopology;
 
@@ -45,7 +46,7 @@ pub enum DetailedType {
     AIntersection,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Coord {
     pub x: i8,
     pub y: i8,
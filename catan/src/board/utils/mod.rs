@@ -1,6 +1,8 @@
 mod coord;
 mod transform;
+mod axial;
 pub mod topology;
 
 pub use coord::{Coord, Type as CoordType, DetailedType as DetailedCoordType};
 pub use transform::{CoordRotation, CoordTransform};
+pub use axial::{Axial, AxialCoord, HexDirection, HexCorner};
@@ -1,7 +1,76 @@
+use std::ops::{Deref, DerefMut};
+
 use super::{Coord, CoordType, DetailedCoordType};
 use crate::board::Error;
 
-pub type TopologyResult = Result<Vec<Coord>, Error>;
+/// Up to 6 neighbouring coords, stored inline so that topology queries don't allocate
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Neighbours {
+    coords: [Coord; Neighbours::CAPACITY],
+    len: u8,
+}
+
+impl Neighbours {
+    pub const CAPACITY: usize = 6;
+    pub const EMPTY: Neighbours = Neighbours { coords: [Coord::ZERO; Neighbours::CAPACITY], len: 0 };
+
+    fn from_slice(coords: &[Coord]) -> Neighbours {
+        let mut neighbours = Neighbours::EMPTY;
+        neighbours.coords[..coords.len()].copy_from_slice(coords);
+        neighbours.len = coords.len() as u8;
+        neighbours
+    }
+
+    /// Only keeps the coords matching the predicate
+    pub fn filtered<F: Fn(Coord) -> bool>(&self, predicate: F) -> Neighbours {
+        let mut neighbours = Neighbours::EMPTY;
+        for coord in self.iter().filter(|coord| predicate(**coord)) {
+            neighbours.coords[neighbours.len as usize] = *coord;
+            neighbours.len += 1;
+        }
+        neighbours
+    }
+}
+
+impl Default for Neighbours {
+    fn default() -> Self {
+        Neighbours::EMPTY
+    }
+}
+
+impl Deref for Neighbours {
+    type Target = [Coord];
+
+    fn deref(&self) -> &[Coord] {
+        &self.coords[..self.len as usize]
+    }
+}
+
+impl DerefMut for Neighbours {
+    fn deref_mut(&mut self) -> &mut [Coord] {
+        &mut self.coords[..self.len as usize]
+    }
+}
+
+impl IntoIterator for Neighbours {
+    type Item = Coord;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Coord, { Neighbours::CAPACITY }>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.coords).take(self.len as usize)
+    }
+}
+
+impl<'a> IntoIterator for &'a Neighbours {
+    type Item = &'a Coord;
+    type IntoIter = std::slice::Iter<'a, Coord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub type TopologyResult = Result<Neighbours, Error>;
 
 pub trait RawTopology {
     fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult;
@@ -27,6 +96,10 @@ fn c(x: i8, y: i8) -> Coord {
     Coord::new(x,y)
 }
 
+macro_rules! neighbours {
+    ($($coord:expr),*) => { Neighbours::from_slice(&[$($coord),*]) };
+}
+
 impl RawTopology for CoordTopology {
     fn neighbours(&self, coord :Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
         if coord.get_type() != center_type {
@@ -35,26 +108,26 @@ impl RawTopology for CoordTopology {
         let x = coord.x;
         let y = coord.y;
         Ok(match (coord.get_detailed_type(), neighbour_type) {
-            (DetailedCoordType::OHex, CoordType::Hex) => vec![c(x+4,y), c(x+2,y+2), c(x-2,y+2), c(x-4,y), c(x-2,y-2), c(x+2,y-2)],
-            (DetailedCoordType::OHex, CoordType::Path) => vec![c(x+2,y), c(x+1,y+1), c(x-1,y+1), c(x-2,y), c(x-1,y-1), c(x+1,y-1)],
-            (DetailedCoordType::OHex, CoordType::Intersection) => vec![c(x+2,y+1), c(x,y+1), c(x-2,y+1), c(x-2,y-1), c(x,y-1), c(x+2,y-1)],
-
-            (DetailedCoordType::IPath, CoordType::Hex) => vec![c(x+2,y), c(x-2,y)],
-            (DetailedCoordType::SPath, CoordType::Hex) => vec![c(x+1,y+1), c(x-1,y-1)],
-            (DetailedCoordType::ZPath, CoordType::Hex) => vec![c(x-1,y+1), c(x+1,y-1)],
-            (DetailedCoordType::IPath, CoordType::Path) => vec![c(x+1,y+1), c(x-1,y+1), c(x-1,y-1), c(x+1,y-1)],
-            (DetailedCoordType::SPath, CoordType::Path) => vec![c(x+2,y), c(x-1,y+1), c(x-2,y), c(x+1,y-1)],
-            (DetailedCoordType::ZPath, CoordType::Path) => vec![c(x+2,y), c(x+1,y+1), c(x-2,y), c(x-1,y-1)],
-            (DetailedCoordType::IPath, CoordType::Intersection) => vec![c(x,y+1), c(x,y-1)],
+            (DetailedCoordType::OHex, CoordType::Hex) => neighbours![c(x+4,y), c(x+2,y+2), c(x-2,y+2), c(x-4,y), c(x-2,y-2), c(x+2,y-2)],
+            (DetailedCoordType::OHex, CoordType::Path) => neighbours![c(x+2,y), c(x+1,y+1), c(x-1,y+1), c(x-2,y), c(x-1,y-1), c(x+1,y-1)],
+            (DetailedCoordType::OHex, CoordType::Intersection) => neighbours![c(x+2,y+1), c(x,y+1), c(x-2,y+1), c(x-2,y-1), c(x,y-1), c(x+2,y-1)],
+
+            (DetailedCoordType::IPath, CoordType::Hex) => neighbours![c(x+2,y), c(x-2,y)],
+            (DetailedCoordType::SPath, CoordType::Hex) => neighbours![c(x+1,y+1), c(x-1,y-1)],
+            (DetailedCoordType::ZPath, CoordType::Hex) => neighbours![c(x-1,y+1), c(x+1,y-1)],
+            (DetailedCoordType::IPath, CoordType::Path) => neighbours![c(x+1,y+1), c(x-1,y+1), c(x-1,y-1), c(x+1,y-1)],
+            (DetailedCoordType::SPath, CoordType::Path) => neighbours![c(x+2,y), c(x-1,y+1), c(x-2,y), c(x+1,y-1)],
+            (DetailedCoordType::ZPath, CoordType::Path) => neighbours![c(x+2,y), c(x+1,y+1), c(x-2,y), c(x-1,y-1)],
+            (DetailedCoordType::IPath, CoordType::Intersection) => neighbours![c(x,y+1), c(x,y-1)],
             (DetailedCoordType::SPath, CoordType::Intersection) |
-            (DetailedCoordType::ZPath, CoordType::Intersection) => vec![c(x+1,y), c(x-1,y)],
-
-            (DetailedCoordType::AIntersection, CoordType::Hex) => vec![c(x+2,y+1), c(x-2,y+1), c(x,y-1)],
-            (DetailedCoordType::VIntersection, CoordType::Hex) => vec![c(x,y+1), c(x-2,y-1), c(x+2,y-1)],
-            (DetailedCoordType::AIntersection, CoordType::Path) => vec![c(x+1,y), c(x,y+1), c(x-1,y)],
-            (DetailedCoordType::VIntersection, CoordType::Path) => vec![c(x+1,y), c(x-1,y), c(x,y-1)],
-            (DetailedCoordType::AIntersection, CoordType::Intersection) => vec![c(x+2,y), c(x,y+2), c(x-2,y)],
-            (DetailedCoordType::VIntersection, CoordType::Intersection) => vec![c(x+2,y), c(x-2,y), c(x,y-2)],
+            (DetailedCoordType::ZPath, CoordType::Intersection) => neighbours![c(x+1,y), c(x-1,y)],
+
+            (DetailedCoordType::AIntersection, CoordType::Hex) => neighbours![c(x+2,y+1), c(x-2,y+1), c(x,y-1)],
+            (DetailedCoordType::VIntersection, CoordType::Hex) => neighbours![c(x,y+1), c(x-2,y-1), c(x+2,y-1)],
+            (DetailedCoordType::AIntersection, CoordType::Path) => neighbours![c(x+1,y), c(x,y+1), c(x-1,y)],
+            (DetailedCoordType::VIntersection, CoordType::Path) => neighbours![c(x+1,y), c(x-1,y), c(x,y-1)],
+            (DetailedCoordType::AIntersection, CoordType::Intersection) => neighbours![c(x+2,y), c(x,y+2), c(x-2,y)],
+            (DetailedCoordType::VIntersection, CoordType::Intersection) => neighbours![c(x+2,y), c(x-2,y), c(x,y-2)],
 
             _ => return Err(Error::InvalidNeighbourTypes { center:center_type , neighbours:neighbour_type }),
         })
@@ -1,19 +1,20 @@
 use rand::Rng;
 
 use crate::state::{State, PlayerId};
-use crate::utils::{Resource, Resources, Hex, LandHex, DevelopmentCard};
+use crate::utils::{Resource, Resources, Hex, LandHex};
 use crate::board::utils::topology::Topology;
 
-use super::{Action, Phase, TurnPhase, DevelopmentPhase, Notification};
+use super::{Action, Phase, TurnPhase, DevelopmentPhase, Notification, GameRandom};
 
 /// Applies a legal action
 ///
 /// Modifies a state by applying a given action, and/or changes the phase action.
 /// The function assumes that the action is legal and that it can be applied without problem.
 /// It is necessary to call [legal](crate::game::legal::legal) beforehand to check if the action can indeed be applied without problem
-pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Action, rng: &mut R) -> Option<Notification> {
+pub fn apply<R : GameRandom>(phase: &mut Phase, state: &mut State, action: Action, rng: &mut R) -> Option<Notification> {
     static ERROR_MESSAGE: &'static str = "Apply function failed because action supplied was illegal";
     let player = phase.player();
+    let mut notification = None;
     match action {
         //
         // ## Ending Turn
@@ -34,7 +35,7 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
         // ## Rolling Dice
         //
         Action::RollDice => {
-            let roll = rng.gen_range(1, 7) + rng.gen_range(1, 7);
+            let roll = rng.roll_dice();
             // ### Rolling 7
             if roll == 7 {
                 let mut discards = Vec::<(PlayerId, Option<Resources>)>::new();
@@ -123,7 +124,7 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
                     let mut total_discards = discarded.total();
                     while total_discards > should_discard {
                         // Randomly keep cards
-                        let mut picked = rng.gen_range(0, total_discards);
+                        let mut picked = rng.events().gen_range(0, total_discards);
                         for res in Resource::ALL.iter() {
                             if picked < discarded[*res] {
                                 discarded[*res] -= 1;
@@ -143,13 +144,15 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
                                 if p == player {
                                     match iter.next() {
                                         Some((next_p, _)) => {
+                                            let discarding = *player;
                                             *player = *next_p;
-                                            return None;
+                                            return Some(Notification::Discarded { player: discarding, resources: discarded });
                                         }
                                         None => {
+                                            let discarding = *player;
                                             state.apply_discards();
                                             *turn_phase = TurnPhase::MoveThief;
-                                            return None; // Should return discard messages
+                                            return Some(Notification::Discarded { player: discarding, resources: discarded });
                                         }
                                     }
                                 }
@@ -168,11 +171,12 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
             if victim != player && victim != PlayerId::NONE {
                 if state.get_player_hand(victim).resources.total() > 0 {
                     let resources = state.get_player_hand(victim).resources;
-                    let mut picked = rng.gen_range(0, resources.total());
+                    let mut picked = rng.events().gen_range(0, resources.total());
                     for res in Resource::ALL.iter() {
                         if picked < resources[*res] {
                             state.get_player_hand_mut(victim).resources[*res] -= 1;
                             state.get_player_hand_mut(player).resources[*res] += 1;
+                            notification = Some(Notification::Stolen { thief: player, victim, resource: Some(*res) });
                             break;
                         } else {
                             picked -= resources[*res];
@@ -233,12 +237,15 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
                 *state.get_bank_resources_mut() += Resources::SETTLEMENT;
             } else if *phase == (Phase::InitialPlacement { player, placing_second: true, placing_road: false }) {
                 // Gives surrounding resources when placing the second settlement of the initial phase
+                let mut received = Resources::ZERO;
                 for hex in state.intersection_hex_neighbours(intersection).expect(ERROR_MESSAGE) {
                     if let Hex::Land(LandHex::Prod(res, _)) = state.get_static_hex(hex).expect(ERROR_MESSAGE) {
-                        state.get_player_hand_mut(player).resources[res] += 1;
-                        state.get_bank_resources_mut()[res] -= 1;
+                        received[res] += 1;
                     }
                 }
+                state.get_player_hand_mut(player).resources += received;
+                *state.get_bank_resources_mut() -= received;
+                notification = Some(Notification::InitialResources { player, resources: received });
             }
             // Checks if an enemy road was broken
             let mut neighbour_players = vec![false; state.player_count() as usize];
@@ -289,17 +296,8 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
         Action::BuyDevelopment => {
             state.get_player_hand_mut(player).resources -= Resources::DVP_CARD;
             *state.get_bank_resources_mut() += Resources::DVP_CARD;
-            let development = state.get_development_cards_mut();
-            let mut picked = rng.gen_range(0, development.total());
-            for dvp in DevelopmentCard::ALL.iter() {
-                if picked < development[*dvp] {
-                    // this development card was picked
-                    development[*dvp] -= 1;
-                    state.get_player_hand_mut(player).new_development_cards[*dvp] += 1;
-                    break;
-                } else {
-                    picked -= development[*dvp];
-                }
+            if let Some(card) = state.get_development_deck_mut().draw(rng.events()) {
+                state.get_player_hand_mut(player).new_development_cards[card] += 1;
             }
         }
         //
@@ -350,15 +348,16 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
         Action::DevelopmentMonopole { resource } => {
             state.get_player_hand_mut(player).development_cards.monopole -= 1;
 
-            let mut total_taken = 0;
+            let mut taken = vec![0; state.player_count() as usize];
             for p in 0..state.player_count() {
                 let p = PlayerId::from(p);
                 if p != player {
-                    total_taken += state.get_player_hand(p).resources[resource];
+                    taken[p.to_usize()] = state.get_player_hand(p).resources[resource];
                     state.get_player_hand_mut(p).resources[resource] = 0;
                 }
             }
-            state.get_player_hand_mut(player).resources[resource] += total_taken;
+            state.get_player_hand_mut(player).resources[resource] += taken.iter().sum::<i8>();
+            notification = Some(Notification::Monopoly { player, resource, taken });
 
             if let Phase::Turn { player: _, turn_phase: _, development_phase } = phase {
                 *development_phase = DevelopmentPhase::DevelopmentPlayed;
@@ -403,5 +402,5 @@ pub(super) fn apply<R : Rng>(phase: &mut Phase, state: &mut State, action: Actio
             }
         }
     }
-    None
+    notification
 }
@@ -1,8 +1,9 @@
-use crate::utils::{Coord, CoordType, Resources, DevelopmentCard};
+use crate::utils::{Coord, CoordType, Resource, Resources, DevelopmentCard};
 use crate::state::{State, PlayerId};
 use crate::game::{Phase, TurnPhase, DevelopmentPhase, Action, Error};
 use crate::board::utils::topology::Topology;
 use crate::board::Error as BoardError;
+use crate::player::ActionSpace;
 
 /// Is the intersection free for a settlement
 ///
@@ -370,3 +371,185 @@ pub fn legal(phase: &Phase, state: &State, action: Action) -> Result<(), Error>
         _ => panic!("Game already finished"),
     }
 }
+
+/// Every legal action in this context
+///
+/// Gives exactly the actions of [generate_possible_actions](crate::player::generate_possible_actions),
+/// made for the seat of the phase's player, accepted by [legal], in the same order.
+/// They are built directly, skipping what the player can't afford and only looking at
+/// reachable paths, free intersections and real victims
+pub fn legal_actions(phase: &Phase, state: &State) -> Vec<Action> {
+    let mut actions = Vec::new();
+    match phase {
+        Phase::InitialPlacement { player, placing_second: _, placing_road: true } => {
+            // Paths next to the settlement that was just placed
+            let mut paths = Vec::new();
+            for intersection in state.get_layout().intersections.iter() {
+                if let Ok(Some((p, _))) = state.get_dynamic_intersection(*intersection) {
+                    if p == *player && connected_position(*intersection, *player, state) == Ok(false) {
+                        paths.extend(state.intersection_path_neighbours(*intersection).unwrap_or_default());
+                    }
+                }
+            }
+            for path in state.get_layout().paths.iter() {
+                if paths.contains(path) {
+                    actions.push(Action::BuildRoad { path: *path });
+                }
+            }
+        }
+        Phase::InitialPlacement { player: _, placing_second: _, placing_road: false } => {
+            for intersection in state.get_layout().intersections.iter() {
+                if available_settlement_position(*intersection, state) == Ok(true) {
+                    actions.push(Action::BuildSettlement { intersection: *intersection });
+                }
+            }
+        }
+        Phase::Turn { player: _, turn_phase: TurnPhase::Discard(discarding), development_phase: _ } => {
+            let current = state.get_player_hand(*discarding).resources;
+            // Discards only keep four cards, see [generate_possible_actions](crate::player::generate_possible_actions)
+            if 4 <= (current.total() + 1) / 2 {
+                for b in 0..5 {
+                    for l in 0..5-b {
+                        for o in 0..5-(b+l) {
+                            for g in 0..5-(b+l+o) {
+                                let resources = Resources::new(b, l, o, g, 4-(b+l+o+g));
+                                if current >= resources {
+                                    actions.push(Action::Keep { resources });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Phase::Turn { player, turn_phase, development_phase } => {
+            let player = *player;
+            let hand = state.get_player_hand(player);
+            let free = *turn_phase == TurnPhase::Free;
+            // ## Hexes: MoveThief
+            if *turn_phase == TurnPhase::MoveThief || *development_phase == DevelopmentPhase::KnightActive {
+                let player_count = state.player_count();
+                for hex in state.get_layout().hexes.iter() {
+                    if *hex == state.get_thief_hex() {
+                        continue;
+                    }
+                    let mut victims = vec![false; player_count as usize];
+                    let buildings: Result<Vec<_>, _> = state.hex_intersection_neighbours(*hex)
+                        .and_then(|intersections| intersections.iter().map(|intersection| state.get_dynamic_intersection(*intersection)).collect());
+                    match buildings {
+                        Ok(buildings) => for (p, _) in buildings.into_iter().flatten() {
+                            if p != player {
+                                victims[p.to_usize()] = true;
+                            }
+                        },
+                        Err(_) => continue,
+                    }
+                    let anyone = victims.iter().any(|victim| *victim);
+                    for p in 0..player_count {
+                        let victim = PlayerId::from((p + player.to_u8()) % player_count);
+                        // Without anyone to steal from, the player picks itself
+                        if victims[victim.to_usize()] || (!anyone && victim == player) {
+                            actions.push(Action::MoveThief { hex: *hex, victim });
+                        }
+                    }
+                }
+            }
+            // ## Paths: BuildRoad
+            let road_building = matches!(development_phase, DevelopmentPhase::RoadBuildingActive { .. }) && turn_phase.unbound();
+            if hand.road_pieces > 0 && (road_building || (free && hand.resources >= Resources::ROAD)) {
+                let mut paths = Vec::new();
+                for path in state.get_layout().paths.iter() {
+                    if state.get_dynamic_path(*path) == Ok(Some(player)) {
+                        paths.extend(state.path_path_neighbours(*path).unwrap_or_default());
+                    }
+                }
+                for path in state.get_layout().paths.iter() {
+                    if paths.contains(path) && state.get_dynamic_path(*path) == Ok(None) {
+                        actions.push(Action::BuildRoad { path: *path });
+                    }
+                }
+            }
+            // ## Intersections: BuildSettlement and BuildCity
+            let settlement = free && hand.settlement_pieces >= 1 && hand.resources >= Resources::SETTLEMENT;
+            let city = free && hand.city_pieces >= 1 && hand.resources >= Resources::CITY;
+            if settlement || city {
+                for intersection in state.get_layout().intersections.iter() {
+                    let building = state.get_dynamic_intersection(*intersection).unwrap_or(None);
+                    if settlement && building.is_none()
+                        && available_settlement_position(*intersection, state) == Ok(true)
+                        && connected_position(*intersection, player, state) == Ok(true) {
+                        actions.push(Action::BuildSettlement { intersection: *intersection });
+                    }
+                    if city && building == Some((player, false)) {
+                        actions.push(Action::BuildCity { intersection: *intersection });
+                    }
+                }
+            }
+            // ## TurnPhase
+            if *turn_phase == TurnPhase::PreRoll {
+                actions.push(Action::RollDice);
+            }
+            if free {
+                actions.push(Action::EndTurn);
+            }
+            // ## Trade
+            if free {
+                let bank = state.get_bank_resources();
+                for given in Resource::ALL.iter() {
+                    if hand.resources[*given] < hand.harbor.rate(*given) as i8 {
+                        continue;
+                    }
+                    for asked in Resource::ALL.iter() {
+                        if given != asked && bank[*asked] > 0 {
+                            actions.push(Action::TradeBank { given: *given, asked: *asked });
+                        }
+                    }
+                }
+            }
+            // ## Development
+            if free && state.get_development_cards().total() >= 1 && hand.resources >= Resources::DVP_CARD {
+                actions.push(Action::BuyDevelopment);
+            }
+            let playable = turn_phase.unbound() && *development_phase == DevelopmentPhase::Ready;
+            let cards = hand.development_cards;
+            if playable && cards.knight > 0 {
+                actions.push(Action::DevelopmentKnight);
+            }
+            if playable && cards.road_building > 0 {
+                actions.push(Action::DevelopmentRoadBuilding);
+            }
+            if playable && cards.year_of_plenty > 0 {
+                actions.push(Action::DevelopmentYearOfPlenty);
+            }
+            if let DevelopmentPhase::YearOfPlentyActive { two_left: _ } = *development_phase {
+                if turn_phase.unbound() {
+                    let bank = state.get_bank_resources();
+                    for resource in Resource::ALL.iter() {
+                        if bank[*resource] != 0 {
+                            actions.push(Action::ChooseFreeResource { resource: *resource });
+                        }
+                    }
+                }
+            }
+            if playable && cards.monopole > 0 {
+                for resource in Resource::ALL.iter() {
+                    actions.push(Action::DevelopmentMonopole { resource: *resource });
+                }
+            }
+        }
+        Phase::FinishedGame { winner: _ } => (),
+    }
+    actions
+}
+
+/// Marks the legal actions at their index in the action space, clearing the others
+///
+/// The space should be the one of the phase's player, legal actions missing from it are skipped
+pub fn legal_actions_mask(phase: &Phase, state: &State, space: &ActionSpace, mask: &mut [bool]) {
+    mask.iter_mut().for_each(|legal| *legal = false);
+    for action in legal_actions(phase, state) {
+        if let Some(legal) = space.index(action).and_then(|index| mask.get_mut(index)) {
+            *legal = true;
+        }
+    }
+}
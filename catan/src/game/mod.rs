@@ -3,25 +3,33 @@ mod error;
 mod phase;
 mod notification;
 mod apply;
+mod validate;
+mod scenario;
+mod notation;
+mod random;
 pub mod legal;
 
 pub use error::Error;
 pub use action::{Action, ActionCategory};
 pub use phase::{Phase, TurnPhase, DevelopmentPhase};
 pub use notification::Notification;
+pub use apply::apply;
+pub use validate::{validate, Violation};
+pub use scenario::Scenario;
+pub use notation::{to_notation, from_notation, NotationError};
+pub(crate) use notation::write_notation;
+pub use random::{GameRandom, GameRng};
 
 // --------------------------------------------------------------------------------------------- //
 
-use rand::SeedableRng;
 use rand::rngs::SmallRng;
 use rand::seq::SliceRandom;
 
-use crate::state::{State, TricellState};
+use crate::state::{State, TricellState, PlayerView};
 use crate::board::setup;
 use crate::state::PlayerId;
 use crate::player::CatanPlayer;
-
-use apply::apply;
+use crate::utils::DevelopmentCards;
 
 pub struct Game {
     pub players: Vec<Box<dyn CatanPlayer>>,
@@ -46,34 +54,42 @@ impl Game {
 
     pub fn setup_and_play(&mut self) -> Notification {
         let player_count = self.players.len();
-        let mut rng = SmallRng::from_entropy();
-        let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, player_count as u8);
+        let mut rng = GameRng::from_entropy();
+        let mut state = setup::random_default::<TricellState, SmallRng>(rng.events(), player_count as u8);
+        *state.get_development_deck_mut() = rng.development_deck(DevelopmentCards::STARTING_DECK);
         let mut players_order: Vec<usize> = (0..player_count).collect();
-        players_order.shuffle(&mut rng);
+        players_order.shuffle(rng.events());
         self.play(&mut rng, &mut state, players_order)
     }
 
-    pub fn play(&mut self, rng: &mut SmallRng, state: &mut State, players_order: Vec<usize>) -> Notification {
-        let mut phase = Phase::START_GAME;
+    pub fn play<R : GameRandom>(&mut self, rng: &mut R, state: &mut State, players_order: Vec<usize>) -> Notification {
+        self.play_from(rng, state, Phase::START_GAME, players_order)
+    }
+
+    /// Plays a game resuming from any position, for instance one made with a [Scenario]
+    pub fn play_from<R : GameRandom>(&mut self, rng: &mut R, state: &mut State, mut phase: Phase, players_order: Vec<usize>) -> Notification {
 
         for (i, player) in players_order.iter().enumerate() {
-            self.players[*player].new_game(PlayerId::from(i), &state);
+            let player = &mut self.players[*player];
+            player.new_game(PlayerId::from(i), &phase, &view(player.as_ref(), state, PlayerId::from(i)));
         }
         loop {
             // If the game is finished, exit
             if let Phase::FinishedGame { winner } = phase {
-                for player in players_order.iter() {
-                    self.players[*player].results(&state, winner);
+                for (i, player) in players_order.iter().enumerate() {
+                    let player = &mut self.players[*player];
+                    player.results(&view(player.as_ref(), state, PlayerId::from(i)), winner);
                 }
                 return Notification::GameFinished { winner };
             }
 
             // Get the player object that is supposed to be making a decision
             let player = &mut self.players[players_order[phase.player().to_u8() as usize]];
+            let player_view = view(player.as_ref(), state, phase.player());
             let mut action;
             loop {
                 // Ask player to take action
-                action = player.pick_action(&phase, &state);
+                action = player.pick_action(&phase, &player_view);
                 if action == Action::Exit {
                     return Notification::GameFinished { winner: PlayerId::NONE };
                 }
@@ -92,38 +108,27 @@ impl Game {
             let prev_phase = phase;
             self.notify_all(Notification::ActionPlayed { by: phase.player(), action });
             // Applies action
-            apply(&mut phase, state, action, rng);
-            let coherence = check_coherence(state);
-            if coherence.is_err() {
+            let outcome = apply(&mut phase, state, action, rng);
+            let violations = validate(state, &phase);
+            if !violations.is_empty() {
                 println!("[INCOHERENCE] {:?} --({:?})-> {:?}", prev_phase, action, phase);
-                panic!("{:?}", coherence.err());
+                panic!("{:?}", violations);
+            }
+            // Notifies every player of the outcome, as they can see it
+            if let Some(notification) = outcome {
+                for (i, player) in players_order.iter().enumerate() {
+                    self.players[*player].notify(&notification.visible_to(PlayerId::from(i)));
+                }
             }
         }
     }
 }
 
-use crate::utils::{Resource, Resources};
-
-fn check_coherence(state: &State) -> Result<(),String> {
-    let mut players_resources = Resources::ZERO;
-    for p in 0..state.player_count() {
-        let player = PlayerId::from(p);
-        let hand = state.get_player_hand(player).resources;
-        for res in Resource::ALL.iter() {
-            let v = hand[*res];
-            if v > 19 || v < 0 {
-                return Err(format!("Player {:?} has {} of {}", player, v, res));
-            }
-        }
-        players_resources += hand;
-    }
-    let bank_resources = state.get_bank_resources();
-    for res in Resource::ALL.iter() {
-        let v = bank_resources[*res];
-        let pv = players_resources[*res];
-        if v > 19 || v < 0 || pv+v != 19 {
-            return Err(format!("For resource {}: Bank has {} / Players have {}", res, v, pv));
-        }
+/// The view a player is allowed to have of its seat
+fn view<'a>(player: &dyn CatanPlayer, state: &'a State, position: PlayerId) -> PlayerView<'a> {
+    if player.oracle() {
+        PlayerView::oracle(state, position)
+    } else {
+        PlayerView::new(state, position)
     }
-    Ok(())
 }
@@ -0,0 +1,421 @@
+use std::fmt::Write;
+
+use crate::utils::{Hex, LandHex, Harbor, Resource, Resources, DevelopmentCard, DevelopmentCards, DevelopmentDeck};
+use crate::state::{State, StateMaker, TricellState, PlayerId};
+use crate::board::layout;
+
+use super::{Phase, TurnPhase, DevelopmentPhase, Violation, Scenario};
+
+/// Reasons a notation couldn't be loaded
+#[derive(Clone, PartialEq, Debug)]
+pub enum NotationError {
+    /// A field is missing
+    MissingField(&'static str),
+    /// A field couldn't be read
+    InvalidField(&'static str, String),
+    /// The position described isn't coherent
+    Violations(Vec<Violation>),
+    /// The position isn't on the default layout
+    UnsupportedLayout,
+}
+
+/// Most players a notation can describe
+const MAX_PLAYERS: usize = 4;
+
+const FIELDS: [&str; 11] = [
+    "hexes", "harbors", "roads", "buildings", "thief", "hands", "bank", "deck", "titles", "phase", "discards",
+];
+
+/// Single line description of a position
+///
+/// Space separated fields, in order:
+/// - hexes: `.` for water, `D` for desert, resource and number token otherwise (`O10`), in layout order
+/// - harbors: `index=harbor` for every intersection with a harbor, `X` being a generic harbor
+/// - roads: path indexes of each player, players separated by `/`
+/// - buildings: `s` or `c` followed by the intersection index, players separated by `/`
+/// - thief: hex index, `-` if off the board
+/// - hands: `resources:cards:new cards:knights:continous road` of each player, players separated by `/`
+/// - bank: amounts of each resource
+/// - deck: cards in drawing order (`KVR`) for an ordered deck, amounts of each card otherwise
+/// - titles: holders of the longest road and largest army
+/// - phase: `i<player>:<1|2>:<s|r>`, `t<player>:<p|d<player>|m|f>:<r|k|b<1|2>|y<1|2>|x>` or `w<player>`
+/// - discards: `player=resources` or `player=?` for each held discard
+///
+/// Empty lists are written `-`. Only the default layout, with 2 to 4 players, is supported
+pub fn to_notation(state: &State, phase: &Phase) -> Result<String, NotationError> {
+    let layout = state.get_layout();
+    let default = &layout::DEFAULT;
+    if layout.hexes != default.hexes || layout.paths != default.paths || layout.intersections != default.intersections {
+        return Err(NotationError::UnsupportedLayout);
+    }
+    let thief = state.get_thief_hex();
+    if !layout.hexes.contains(&thief) {
+        return Err(NotationError::Violations(vec![Violation::ThiefNotOnLand { hex: thief }]));
+    }
+    Ok(write_notation(state, phase))
+}
+
+/// Notation of a position in the order of its own layout
+///
+/// Only comparable between positions sharing a layout, and only readable back on the default one
+pub(crate) fn write_notation(state: &State, phase: &Phase) -> String {
+    let layout = state.get_layout();
+    let players: Vec<PlayerId> = (0..state.player_count()).map(PlayerId::from).collect();
+    let mut fields = Vec::new();
+    // Static board
+    fields.push(list(layout.hexes.iter().map(|hex| match state.get_static_hex(*hex).unwrap() {
+        Hex::Water => String::from("."),
+        Hex::Land(LandHex::Desert) => String::from("D"),
+        Hex::Land(LandHex::Prod(res, num)) => format!("{}{}", res, num),
+    }), ","));
+    fields.push(list(layout.intersections.iter().enumerate().filter_map(|(i, intersection)| match state.get_static_harbor(*intersection).unwrap() {
+        Harbor::None => None,
+        Harbor::Generic => Some(format!("{}=X", i)),
+        Harbor::Special(res) => Some(format!("{}={}", i, res)),
+    }), ","));
+    // Dynamic board
+    fields.push(list(players.iter().map(|player| list(layout.paths.iter().enumerate().filter_map(|(i, path)|
+        if state.get_dynamic_path(*path).unwrap() == Some(*player) { Some(i.to_string()) } else { None }
+    ), ",")), "/"));
+    fields.push(list(players.iter().map(|player| list(layout.intersections.iter().enumerate().filter_map(|(i, intersection)|
+        match state.get_dynamic_intersection(*intersection).unwrap() {
+            Some((p, is_city)) if p == *player => Some(format!("{}{}", if is_city { "c" } else { "s" }, i)),
+            _ => None,
+        }
+    ), ",")), "/"));
+    fields.push(match layout.hexes.iter().position(|hex| *hex == state.get_thief_hex()) {
+        Some(i) => i.to_string(),
+        None => String::from("-"),
+    });
+    // Hands
+    fields.push(list(players.iter().map(|player| {
+        let hand = state.get_player_hand(*player);
+        format!("{}:{}:{}:{}:{}",
+            resources(hand.resources),
+            development_cards(hand.development_cards),
+            development_cards(hand.new_development_cards),
+            hand.knights,
+            hand.continous_road,
+        )
+    }), "/"));
+    fields.push(resources(state.get_bank_resources()));
+    fields.push(match state.get_development_deck() {
+        DevelopmentDeck::Ordered(stack) => list(stack.iter().rev().map(|card| card.to_string()), ""),
+        DevelopmentDeck::OnDemand(cards) => development_cards(*cards),
+    });
+    fields.push(format!("{},{}",
+        holder(state.get_longest_road().map(|(player, _)| player)),
+        holder(state.get_largest_army().map(|(player, _)| player)),
+    ));
+    fields.push(phase_notation(phase));
+    fields.push(list(state.peek_discards().iter().map(|(player, discard)| match discard {
+        Some(discard) => format!("{}={}", player.to_u8(), resources(*discard)),
+        None => format!("{}=?", player.to_u8()),
+    }), "/"));
+    fields.join(" ")
+}
+
+/// Loads a position written with [to_notation]
+pub fn from_notation(notation: &str) -> Result<(State, Phase), NotationError> {
+    let layout = layout::DEFAULT.clone();
+    let mut tokens = notation.split_whitespace();
+    let mut fields = Vec::new();
+    for field in FIELDS.iter() {
+        fields.push(tokens.next().ok_or(NotationError::MissingField(field))?);
+    }
+    let hands: Vec<&str> = items(fields[5], '/');
+    let player_count = hands.len();
+    if !(2..=MAX_PLAYERS).contains(&player_count) {
+        return Err(invalid(5, fields[5]));
+    }
+    if items(fields[2], '/').len() > player_count {
+        return Err(invalid(2, fields[2]));
+    }
+    if items(fields[3], '/').len() > player_count {
+        return Err(invalid(3, fields[3]));
+    }
+    let mut state = TricellState::new_empty(layout.clone(), player_count as u8);
+    // Static board
+    let hexes = items(fields[0], ',');
+    if hexes.len() != layout.hexes.len() {
+        return Err(invalid(0, fields[0]));
+    }
+    for (coord, token) in layout.hexes.iter().zip(hexes) {
+        let hex = match token {
+            "." => Hex::Water,
+            "D" => Hex::Land(LandHex::Desert),
+            _ => {
+                let resource = parse_resource(token.get(..1).unwrap_or("")).ok_or_else(|| invalid(0, token))?;
+                let num = token.get(1..).unwrap_or("").parse().ok().filter(|num| (2..=12).contains(num) && *num != 7).ok_or_else(|| invalid(0, token))?;
+                Hex::Land(LandHex::Prod(resource, num))
+            }
+        };
+        state.set_static_hex(*coord, hex).map_err(|_| invalid(0, token))?;
+    }
+    for token in items(fields[1], ',') {
+        let (index, harbor) = split_pair(token, '=').ok_or_else(|| invalid(1, token))?;
+        let harbor = match harbor {
+            "X" => Harbor::Generic,
+            _ => Harbor::Special(parse_resource(harbor).ok_or_else(|| invalid(1, token))?),
+        };
+        let intersection = index_in(&layout.intersections, index).ok_or_else(|| invalid(1, token))?;
+        state.set_static_harbor(intersection, harbor).map_err(|_| invalid(1, token))?;
+    }
+    let mut scenario = Scenario::from_state(state);
+    // Dynamic board
+    for (p, roads) in items(fields[2], '/').into_iter().enumerate() {
+        for token in items(roads, ',') {
+            let path = index_in(&layout.paths, token).ok_or_else(|| invalid(2, token))?;
+            scenario = scenario.road(PlayerId::from(p), path);
+        }
+    }
+    for (p, buildings) in items(fields[3], '/').into_iter().enumerate() {
+        for token in items(buildings, ',') {
+            let intersection = index_in(&layout.intersections, token.get(1..).unwrap_or("")).ok_or_else(|| invalid(3, token))?;
+            scenario = match token.get(..1) {
+                Some("s") => scenario.settlement(PlayerId::from(p), intersection),
+                Some("c") => scenario.city(PlayerId::from(p), intersection),
+                _ => return Err(invalid(3, token)),
+            };
+        }
+    }
+    scenario = scenario.thief(index_in(&layout.hexes, fields[4]).ok_or_else(|| invalid(4, fields[4]))?);
+    // Hands
+    for (p, hand) in hands.into_iter().enumerate() {
+        let player = PlayerId::from(p);
+        let parts: Vec<&str> = hand.split(':').collect();
+        if parts.len() != 5 {
+            return Err(invalid(5, hand));
+        }
+        scenario = scenario
+            .resources(player, parse_resources(parts[0]).ok_or_else(|| invalid(5, hand))?)
+            .development_cards(player, parse_development_cards(parts[1]).ok_or_else(|| invalid(5, hand))?)
+            .new_development_cards(player, parse_development_cards(parts[2]).ok_or_else(|| invalid(5, hand))?)
+            .knights(player, parts[3].parse().ok().filter(|knights| *knights <= DevelopmentCards::STARTING_DECK.knight).ok_or_else(|| invalid(5, hand))?)
+            .continous_road(player, parts[4].parse().map_err(|_| invalid(5, hand))?);
+    }
+    scenario = scenario
+        .bank(parse_resources(fields[6]).ok_or_else(|| invalid(6, fields[6]))?)
+        .development_deck(parse_development_deck(fields[7]).ok_or_else(|| invalid(7, fields[7]))?);
+    let (longest_road, largest_army) = split_pair(fields[8], ',').ok_or_else(|| invalid(8, fields[8]))?;
+    scenario = scenario
+        .longest_road(parse_holder(longest_road, player_count).ok_or_else(|| invalid(8, fields[8]))?)
+        .largest_army(parse_holder(largest_army, player_count).ok_or_else(|| invalid(8, fields[8]))?)
+        .phase(parse_phase(fields[9], player_count).ok_or_else(|| invalid(9, fields[9]))?);
+    let mut discards = Vec::new();
+    for token in items(fields[10], '/') {
+        let (player, discard) = split_pair(token, '=').ok_or_else(|| invalid(10, token))?;
+        let player = parse_player(player, player_count).ok_or_else(|| invalid(10, token))?;
+        let discard = match discard {
+            "?" => None,
+            _ => Some(parse_resources(discard).ok_or_else(|| invalid(10, token))?),
+        };
+        discards.push((player, discard));
+    }
+    scenario.discards(discards).build().map_err(NotationError::Violations)
+}
+
+// --- writing --- //
+
+fn list<I: Iterator<Item = String>>(items: I, separator: &str) -> String {
+    let items: Vec<String> = items.collect();
+    if items.iter().all(|item| item == "-") {
+        String::from("-")
+    } else {
+        items.join(separator)
+    }
+}
+
+fn resources(resources: Resources) -> String {
+    let mut result = String::new();
+    for (i, resource) in Resource::ALL.iter().enumerate() {
+        if i > 0 {
+            result.push(',');
+        }
+        write!(result, "{}", resources[*resource]).unwrap();
+    }
+    result
+}
+
+fn development_cards(cards: DevelopmentCards) -> String {
+    let counts: Vec<String> = DevelopmentCard::ALL.iter().map(|card| cards[*card].to_string()).collect();
+    counts.join(",")
+}
+
+fn holder(holder: Option<PlayerId>) -> String {
+    match holder {
+        Some(player) => player.to_u8().to_string(),
+        None => String::from("-"),
+    }
+}
+
+fn phase_notation(phase: &Phase) -> String {
+    match phase {
+        Phase::InitialPlacement { player, placing_second, placing_road } => format!("i{}:{}:{}",
+            player.to_u8(),
+            if *placing_second { 2 } else { 1 },
+            if *placing_road { "r" } else { "s" },
+        ),
+        Phase::Turn { player, turn_phase, development_phase } => format!("t{}:{}:{}",
+            player.to_u8(),
+            match turn_phase {
+                TurnPhase::PreRoll => String::from("p"),
+                TurnPhase::Discard(discarding) => format!("d{}", discarding.to_u8()),
+                TurnPhase::MoveThief => String::from("m"),
+                TurnPhase::Free => String::from("f"),
+            },
+            match development_phase {
+                DevelopmentPhase::Ready => "r",
+                DevelopmentPhase::KnightActive => "k",
+                DevelopmentPhase::RoadBuildingActive { two_left: true } => "b2",
+                DevelopmentPhase::RoadBuildingActive { two_left: false } => "b1",
+                DevelopmentPhase::YearOfPlentyActive { two_left: true } => "y2",
+                DevelopmentPhase::YearOfPlentyActive { two_left: false } => "y1",
+                DevelopmentPhase::DevelopmentPlayed => "x",
+            },
+        ),
+        Phase::FinishedGame { winner } => format!("w{}", winner.to_u8()),
+    }
+}
+
+// --- reading --- //
+
+fn invalid(field: usize, token: &str) -> NotationError {
+    NotationError::InvalidField(FIELDS[field], String::from(token))
+}
+
+/// Elements of a list, where `-` is an empty element
+fn items(field: &str, separator: char) -> Vec<&str> {
+    if field == "-" || field.is_empty() {
+        Vec::new()
+    } else {
+        field.split(separator).collect()
+    }
+}
+
+fn split_pair(token: &str, separator: char) -> Option<(&str, &str)> {
+    let mut parts = token.splitn(2, separator);
+    Some((parts.next()?, parts.next()?))
+}
+
+fn index_in<T: Copy>(values: &[T], index: &str) -> Option<T> {
+    values.get(index.parse::<usize>().ok()?).copied()
+}
+
+fn parse_resource(token: &str) -> Option<Resource> {
+    match token {
+        "B" => Some(Resource::Brick),
+        "L" => Some(Resource::Lumber),
+        "O" => Some(Resource::Ore),
+        "G" => Some(Resource::Grain),
+        "W" => Some(Resource::Wool),
+        _ => None,
+    }
+}
+
+fn parse_counts(token: &str) -> Option<Vec<i8>> {
+    let counts: Result<Vec<i8>, _> = token.split(',').map(|count| count.parse()).collect();
+    counts.ok().filter(|counts| counts.len() == 5)
+}
+
+/// Amounts of each resource, none above what the bank starts with
+fn parse_resources(token: &str) -> Option<Resources> {
+    let c = parse_counts(token)?;
+    let resources = Resources::new(c[0], c[1], c[2], c[3], c[4]);
+    for resource in Resource::ALL.iter() {
+        if !(0..=Resources::STARTING_BANK[*resource]).contains(&resources[*resource]) {
+            return None;
+        }
+    }
+    Some(resources)
+}
+
+/// Amounts of each card, none above what the deck starts with
+fn parse_development_cards(token: &str) -> Option<DevelopmentCards> {
+    let counts = parse_counts(token)?;
+    let mut cards = DevelopmentCards::new();
+    for (card, count) in DevelopmentCard::ALL.iter().zip(counts) {
+        if count < 0 || count as u8 > DevelopmentCards::STARTING_DECK[*card] {
+            return None;
+        }
+        cards[*card] = count as u8;
+    }
+    Some(cards)
+}
+
+fn parse_development_deck(token: &str) -> Option<DevelopmentDeck> {
+    if token.contains(',') {
+        return parse_development_cards(token).map(DevelopmentDeck::OnDemand);
+    }
+    let mut stack = Vec::new();
+    let mut counts = DevelopmentCards::new();
+    let cards = if token == "-" { "" } else { token };
+    for card in cards.chars().rev() {
+        let card = match card {
+            'K' => DevelopmentCard::Knight,
+            'R' => DevelopmentCard::RoadBuilding,
+            'Y' => DevelopmentCard::YearOfPlenty,
+            'M' => DevelopmentCard::Monopole,
+            'V' => DevelopmentCard::VictoryPoint,
+            _ => return None,
+        };
+        counts[card] += 1;
+        if counts[card] > DevelopmentCards::STARTING_DECK[card] {
+            return None;
+        }
+        stack.push(card);
+    }
+    Some(DevelopmentDeck::Ordered(stack))
+}
+
+fn parse_holder(token: &str, player_count: usize) -> Option<Option<PlayerId>> {
+    match token {
+        "-" => Some(None),
+        _ => parse_player(token, player_count).map(Some),
+    }
+}
+
+/// Player of the game, out of range ids being rejected
+fn parse_player(token: &str, player_count: usize) -> Option<PlayerId> {
+    token.parse::<u8>().ok().map(PlayerId::from).filter(|player| player.to_usize() < player_count)
+}
+
+fn parse_phase(token: &str, player_count: usize) -> Option<Phase> {
+    let parts: Vec<&str> = token.get(1..)?.split(':').collect();
+    match (token.get(..1)?, parts.as_slice()) {
+        ("i", [player, second, road]) => Some(Phase::InitialPlacement {
+            player: parse_player(player, player_count)?,
+            placing_second: match *second { "1" => false, "2" => true, _ => return None },
+            placing_road: match *road { "s" => false, "r" => true, _ => return None },
+        }),
+        ("t", [player, turn, development]) => Some(Phase::Turn {
+            player: parse_player(player, player_count)?,
+            turn_phase: match *turn {
+                "p" => TurnPhase::PreRoll,
+                "m" => TurnPhase::MoveThief,
+                "f" => TurnPhase::Free,
+                _ if turn.starts_with('d') => TurnPhase::Discard(parse_player(turn.get(1..)?, player_count)?),
+                _ => return None,
+            },
+            development_phase: match *development {
+                "r" => DevelopmentPhase::Ready,
+                "k" => DevelopmentPhase::KnightActive,
+                "b2" => DevelopmentPhase::RoadBuildingActive { two_left: true },
+                "b1" => DevelopmentPhase::RoadBuildingActive { two_left: false },
+                "y2" => DevelopmentPhase::YearOfPlentyActive { two_left: true },
+                "y1" => DevelopmentPhase::YearOfPlentyActive { two_left: false },
+                "x" => DevelopmentPhase::DevelopmentPlayed,
+                _ => return None,
+            },
+        }),
+        ("w", [winner]) => {
+            let winner = PlayerId::from(winner.parse::<u8>().ok()?);
+            // Drawn games have no winner
+            if winner != PlayerId::NONE && winner.to_usize() >= player_count {
+                return None;
+            }
+            Some(Phase::FinishedGame { winner })
+        }
+        _ => None,
+    }
+}
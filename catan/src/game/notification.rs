@@ -1,4 +1,4 @@
-use crate::utils::Resources;
+use crate::utils::{Resource, Resources};
 use crate::game::Action;
 use crate::state::PlayerId;
 
@@ -12,9 +12,46 @@ pub enum Notification {
         roll: u8,
         resources: Vec<Resources>,
     },
+    /// Resources received when placing the second settlement
+    InitialResources {
+        player: PlayerId,
+        resources: Resources,
+    },
+    /// A card was stolen with the thief
+    ///
+    /// The resource is only known by the thief and the victim, see [visible_to](Notification::visible_to)
+    Stolen {
+        thief: PlayerId,
+        victim: PlayerId,
+        resource: Option<Resource>,
+    },
+    Discarded {
+        player: PlayerId,
+        resources: Resources,
+    },
+    /// Resources taken by a monopole, indexed by player
+    Monopoly {
+        player: PlayerId,
+        resource: Resource,
+        taken: Vec<i8>,
+    },
     GameFinished {
         winner: PlayerId,
     },
     ThiefRolled,
     InitialPlacementFinished,
 }
+
+impl Notification {
+    /// The notification as seen by a player
+    ///
+    /// Hides the private information the player shouldn't know about
+    pub fn visible_to(&self, player: PlayerId) -> Notification {
+        match self {
+            Notification::Stolen { thief, victim, resource: Some(_) } if player != *thief && player != *victim => {
+                Notification::Stolen { thief: *thief, victim: *victim, resource: None }
+            }
+            notification => notification.clone(),
+        }
+    }
+}
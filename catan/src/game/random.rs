@@ -0,0 +1,72 @@
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::SmallRng;
+
+use crate::utils::{DevelopmentCards, DevelopmentDeck};
+
+/// Source of the randomness of a game
+///
+/// Any [Rng] can be used, every random outcome then coming from the same stream.
+/// A [GameRng] keeps the dice apart, so that they don't depend on the actions taken
+pub trait GameRandom {
+    type Events : Rng;
+
+    /// Sum of two dice
+    fn roll_dice(&mut self) -> u8;
+
+    /// Randomness of everything else: steals, discards, and cards drawn from unordered decks
+    fn events(&mut self) -> &mut Self::Events;
+}
+
+impl<R : Rng> GameRandom for R {
+    type Events = R;
+
+    fn roll_dice(&mut self) -> u8 {
+        self.gen_range(1, 7) + self.gen_range(1, 7)
+    }
+
+    fn events(&mut self) -> &mut R {
+        self
+    }
+}
+
+/// Randomness of a game split in independent streams
+///
+/// Two games with the same seed roll the same dice and shuffle the same
+/// development deck, whatever the players do
+pub struct GameRng {
+    dice: SmallRng,
+    deck: SmallRng,
+    events: SmallRng,
+}
+
+impl GameRng {
+    pub fn seed_from_u64(seed: u64) -> GameRng {
+        let mut seeds = SmallRng::seed_from_u64(seed);
+        GameRng {
+            dice: SmallRng::seed_from_u64(seeds.next_u64()),
+            deck: SmallRng::seed_from_u64(seeds.next_u64()),
+            events: SmallRng::seed_from_u64(seeds.next_u64()),
+        }
+    }
+
+    pub fn from_entropy() -> GameRng {
+        GameRng::seed_from_u64(SmallRng::from_entropy().next_u64())
+    }
+
+    /// Ordered deck of the given cards, shuffled with the deck stream
+    pub fn development_deck(&mut self, cards: DevelopmentCards) -> DevelopmentDeck {
+        DevelopmentDeck::shuffled(cards, &mut self.deck)
+    }
+}
+
+impl GameRandom for GameRng {
+    type Events = SmallRng;
+
+    fn roll_dice(&mut self) -> u8 {
+        self.dice.gen_range(1, 7) + self.dice.gen_range(1, 7)
+    }
+
+    fn events(&mut self) -> &mut SmallRng {
+        &mut self.events
+    }
+}
@@ -0,0 +1,252 @@
+use crate::utils::{Coord, Hex, Harbor, Resources, DevelopmentCard, DevelopmentCards, DevelopmentDeck};
+use crate::state::{State, StateMaker, TricellState, PlayerId};
+use crate::board::layout;
+use crate::board::utils::topology::Topology;
+use crate::board::Error as BoardError;
+
+use super::{Phase, TurnPhase, Violation, validate};
+
+/// Builder for arbitrary positions
+///
+/// Places tiles, harbors, pieces and the thief, sets hands, the development deck and the bank,
+/// then picks the phase the game should resume from.
+/// Pieces are taken from the hands of their owners, so that [build](Scenario::build) only returns
+/// positions that pass [validate]
+pub struct Scenario {
+    state: State,
+    phase: Option<Phase>,
+    bank: Option<Resources>,
+    development_deck: Option<DevelopmentDeck>,
+    longest_road: Option<Option<PlayerId>>,
+    largest_army: Option<Option<PlayerId>>,
+    discards: Option<Vec<(PlayerId, Option<Resources>)>>,
+    continous_roads: Vec<(PlayerId, u8)>,
+    errors: Vec<Violation>,
+}
+
+impl Scenario {
+    /// Starts from an empty default board, with only water hexes
+    pub fn new(player_count: u8) -> Scenario {
+        Scenario::from_state(TricellState::new_empty(layout::DEFAULT.clone(), player_count))
+    }
+
+    /// Starts from an existing state, for instance a randomly [setup](crate::board::setup) board
+    pub fn from_state(state: State) -> Scenario {
+        Scenario {
+            state,
+            phase: None,
+            bank: None,
+            development_deck: None,
+            longest_road: None,
+            largest_army: None,
+            discards: None,
+            continous_roads: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn check(&mut self, result: Result<(), BoardError>) {
+        if let Err(error) = result {
+            self.errors.push(Violation::Board(error));
+        }
+    }
+
+    // Static Board
+
+    pub fn hex(mut self, coord: Coord, hex: Hex) -> Scenario {
+        let result = self.state.set_static_hex(coord, hex);
+        self.check(result);
+        self
+    }
+
+    /// Places a harbor on the two intersections of a coastal path
+    pub fn harbor(mut self, path: Coord, harbor: Harbor) -> Scenario {
+        let result = self.state.path_intersection_neighbours(path).and_then(|intersections| {
+            for intersection in intersections {
+                self.state.set_static_harbor(intersection, harbor)?;
+            }
+            Ok(())
+        });
+        self.check(result);
+        self
+    }
+
+    pub fn thief(mut self, hex: Coord) -> Scenario {
+        self.state.set_thief_hex(hex);
+        self
+    }
+
+    // Dynamic Board
+
+    pub fn road(mut self, player: PlayerId, path: Coord) -> Scenario {
+        let result = self.state.set_dynamic_path(path, player);
+        self.check(result);
+        let hand = self.state.get_player_hand_mut(player);
+        hand.road_pieces = hand.road_pieces.saturating_sub(1);
+        self
+    }
+
+    pub fn settlement(mut self, player: PlayerId, intersection: Coord) -> Scenario {
+        let result = self.state.set_dynamic_intersection(intersection, player, false)
+            .and_then(|_| self.state.get_static_harbor(intersection));
+        let hand = self.state.get_player_hand_mut(player);
+        hand.settlement_pieces = hand.settlement_pieces.saturating_sub(1);
+        hand.building_vp += 1;
+        match result {
+            Ok(harbor) => hand.harbor.add(harbor),
+            Err(error) => self.errors.push(Violation::Board(error)),
+        }
+        self
+    }
+
+    pub fn city(mut self, player: PlayerId, intersection: Coord) -> Scenario {
+        let result = self.state.set_dynamic_intersection(intersection, player, true)
+            .and_then(|_| self.state.get_static_harbor(intersection));
+        let hand = self.state.get_player_hand_mut(player);
+        hand.city_pieces = hand.city_pieces.saturating_sub(1);
+        hand.building_vp += 2;
+        match result {
+            Ok(harbor) => hand.harbor.add(harbor),
+            Err(error) => self.errors.push(Violation::Board(error)),
+        }
+        self
+    }
+
+    // Hands
+
+    pub fn resources(mut self, player: PlayerId, resources: Resources) -> Scenario {
+        self.state.get_player_hand_mut(player).resources = resources;
+        self
+    }
+
+    /// Development cards that can be played this turn
+    pub fn development_cards(mut self, player: PlayerId, cards: DevelopmentCards) -> Scenario {
+        self.state.get_player_hand_mut(player).development_cards = cards;
+        self
+    }
+
+    /// Development cards bought this turn
+    pub fn new_development_cards(mut self, player: PlayerId, cards: DevelopmentCards) -> Scenario {
+        self.state.get_player_hand_mut(player).new_development_cards = cards;
+        self
+    }
+
+    /// Number of knights already played
+    pub fn knights(mut self, player: PlayerId, knights: u8) -> Scenario {
+        self.state.get_player_hand_mut(player).knights = knights;
+        self
+    }
+
+    // Rest of the state
+
+    /// Content of the bank
+    ///
+    /// By default, the bank holds the resources that aren't in any hand
+    pub fn bank(mut self, resources: Resources) -> Scenario {
+        self.bank = Some(resources);
+        self
+    }
+
+    /// Content of the development deck
+    ///
+    /// By default, the cards of the starting deck that aren't in any hand or played are picked on demand
+    pub fn development_deck(mut self, deck: DevelopmentDeck) -> Scenario {
+        self.development_deck = Some(deck);
+        self
+    }
+
+    /// Length of the longest continous road of a player
+    ///
+    /// By default, it is computed from the roads on the board
+    pub fn continous_road(mut self, player: PlayerId, length: u8) -> Scenario {
+        self.continous_roads.push((player, length));
+        self
+    }
+
+    /// Holder of the longest road title, only needed to break ties
+    pub fn longest_road(mut self, holder: Option<PlayerId>) -> Scenario {
+        self.longest_road = Some(holder);
+        self
+    }
+
+    /// Holder of the largest army title, only needed to break ties
+    pub fn largest_army(mut self, holder: Option<PlayerId>) -> Scenario {
+        self.largest_army = Some(holder);
+        self
+    }
+
+    /// Discards chosen, or not yet chosen, during a discard phase
+    ///
+    /// By default, every player with 7 or more cards still has to discard
+    pub fn discards(mut self, discards: Vec<(PlayerId, Option<Resources>)>) -> Scenario {
+        self.discards = Some(discards);
+        self
+    }
+
+    /// Phase to resume the game from
+    ///
+    /// By default, [START_GAME](Phase::START_GAME) if the board is empty, and [START_TURNS](Phase::START_TURNS) otherwise
+    pub fn phase(mut self, phase: Phase) -> Scenario {
+        self.phase = Some(phase);
+        self
+    }
+
+    /// Finishes the position, and checks that it is coherent
+    pub fn build(mut self) -> Result<(State, Phase), Vec<Violation>> {
+        let player_count = self.state.player_count();
+        // Bank and development deck
+        let mut bank = Resources::STARTING_BANK;
+        let mut deck = DevelopmentCards::STARTING_DECK;
+        for p in 0..player_count {
+            let hand = self.state.get_player_hand(PlayerId::from(p));
+            bank -= hand.resources;
+            for card in DevelopmentCard::ALL.iter() {
+                deck[*card] = deck[*card].saturating_sub(hand.development_cards[*card] + hand.new_development_cards[*card]);
+            }
+            deck.knight = deck.knight.saturating_sub(hand.knights);
+        }
+        *self.state.get_bank_resources_mut() = self.bank.unwrap_or(bank);
+        *self.state.get_development_deck_mut() = self.development_deck.take().unwrap_or(DevelopmentDeck::OnDemand(deck));
+        // Titles
+        for p in 0..player_count {
+            self.state.reset_longest_road(PlayerId::from(p));
+            self.state.update_largest_army(PlayerId::from(p));
+        }
+        for (player, length) in self.continous_roads.iter() {
+            self.state.get_player_hand_mut(*player).continous_road = *length;
+        }
+        if let Some(holder) = self.longest_road {
+            self.state.set_longest_road(holder);
+        }
+        if let Some(holder) = self.largest_army {
+            self.state.set_largest_army(holder);
+        }
+        // Phase
+        let built = (0..player_count).any(|p| self.state.get_player_hand(PlayerId::from(p)).building_vp > 0);
+        let phase = match self.phase {
+            Some(phase) => phase,
+            None if built => Phase::START_TURNS,
+            None => Phase::START_GAME,
+        };
+        if let Phase::Turn { player: _, turn_phase: TurnPhase::Discard(_), development_phase: _ } = phase {
+            let discards = self.discards.take().unwrap_or_else(||
+                (0..player_count)
+                    .map(PlayerId::from)
+                    .filter(|player| self.state.get_player_hand(*player).resources.total() >= 7)
+                    .map(|player| (player, None))
+                    .collect()
+            );
+            self.state.hold_discards(discards);
+        } else if let Some(discards) = self.discards.take() {
+            self.state.hold_discards(discards);
+        }
+        // Validation
+        let mut violations = self.errors;
+        violations.extend(validate(&self.state, &phase));
+        if violations.is_empty() {
+            Ok((self.state, phase))
+        } else {
+            Err(violations)
+        }
+    }
+}
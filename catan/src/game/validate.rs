@@ -0,0 +1,346 @@
+use crate::utils::{Coord, Hex, Resource, Resources, DevelopmentCard, DevelopmentCards};
+use crate::state::{State, PlayerId, PlayerHand};
+use crate::board::utils::topology::Topology;
+use crate::board::Error as BoardError;
+
+use super::{Phase, TurnPhase};
+
+/// Broken invariant of a state
+///
+/// Returned by [validate], each violation describes one way in which a state
+/// could not have been reached by playing legal actions from a starting position
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Violation {
+    /// The bank and the players don't hold the 19 cards of a resource
+    ResourceCount {
+        resource: Resource,
+        bank: i8,
+        players: i8,
+    },
+    /// A player holds a negative amount of a resource
+    NegativeResource {
+        player: PlayerId,
+        resource: Resource,
+    },
+    /// The bank holds a negative amount of a resource
+    NegativeBank {
+        resource: Resource,
+    },
+    /// More cards of a type are in the game than in the starting deck
+    DevelopmentCardCount {
+        card: DevelopmentCard,
+        counted: u8,
+    },
+    /// The pieces in a player's hand and on the board don't add up
+    PieceCount {
+        player: PlayerId,
+        piece: u8, // Road: 0, Settlement: 1, City: 2
+        in_hand: u8,
+        on_board: u8,
+    },
+    /// The victory points given by buildings don't match the board
+    BuildingVictoryPoints {
+        player: PlayerId,
+        recorded: u8,
+        expected: u8,
+    },
+    /// Two buildings are next to each other
+    DistanceRule {
+        intersection: Coord,
+        neighbour: Coord,
+    },
+    /// A road isn't linked to any of its owner's buildings
+    DisconnectedRoad {
+        player: PlayerId,
+        path: Coord,
+    },
+    /// A player has enough points to win but the game isn't finished
+    UndeclaredWinner {
+        player: PlayerId,
+        vp: u8,
+    },
+    /// The game is finished but the winner doesn't have enough points
+    UndeservedWinner {
+        player: PlayerId,
+        vp: u8,
+    },
+    /// The thief isn't on a land hex
+    ThiefNotOnLand {
+        hex: Coord,
+    },
+    /// A player's continous road is longer than its number of roads
+    ContinousRoadTooLong {
+        player: PlayerId,
+        length: u8,
+        roads: u8,
+    },
+    /// The longest road holder isn't (or should be) someone else
+    LongestRoad {
+        holder: Option<PlayerId>,
+    },
+    /// The largest army holder isn't (or should be) someone else
+    LargestArmy {
+        holder: Option<PlayerId>,
+    },
+    /// The held discards don't match the discard phase
+    Discards {
+        player: PlayerId,
+    },
+    /// A coord of the layout couldn't be read
+    Board(BoardError),
+}
+
+impl From<BoardError> for Violation {
+    fn from(board_error: BoardError) -> Self {
+        Violation::Board(board_error)
+    }
+}
+
+/// Checks the invariants of a state
+///
+/// Returns every [Violation] found in the state, given the phase it is in.
+/// An empty list means that the state is coherent
+pub fn validate(state: &State, phase: &Phase) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    check_resources(state, &mut violations);
+    check_development_cards(state, &mut violations);
+    if let Err(error) = check_board(state, &mut violations) {
+        violations.push(Violation::Board(error));
+    }
+    check_victory_points(state, phase, &mut violations);
+    check_longest_road(state, &mut violations);
+    check_largest_army(state, &mut violations);
+    check_discards(state, phase, &mut violations);
+    violations
+}
+
+fn hands(state: &State) -> impl Iterator<Item = (PlayerId, &PlayerHand)> {
+    (0..state.player_count()).map(move |p| {
+        let player = PlayerId::from(p);
+        (player, state.get_player_hand(player))
+    })
+}
+
+fn check_resources(state: &State, violations: &mut Vec<Violation>) {
+    let mut players_resources = Resources::ZERO;
+    for (player, hand) in hands(state) {
+        for resource in Resource::ALL.iter() {
+            if hand.resources[*resource] < 0 {
+                violations.push(Violation::NegativeResource { player, resource: *resource });
+            }
+            // Saturates so that absurd hands are reported instead of overflowing
+            players_resources[*resource] = players_resources[*resource].saturating_add(hand.resources[*resource]);
+        }
+    }
+    let bank = state.get_bank_resources();
+    for resource in Resource::ALL.iter() {
+        if bank[*resource] < 0 {
+            violations.push(Violation::NegativeBank { resource: *resource });
+        }
+        if bank[*resource].saturating_add(players_resources[*resource]) != Resources::STARTING_BANK[*resource] {
+            violations.push(Violation::ResourceCount {
+                resource: *resource,
+                bank: bank[*resource],
+                players: players_resources[*resource],
+            });
+        }
+    }
+}
+
+fn check_development_cards(state: &State, violations: &mut Vec<Violation>) {
+    let mut counted = state.get_development_cards();
+    for (_, hand) in hands(state) {
+        for card in DevelopmentCard::ALL.iter() {
+            counted[*card] = counted[*card]
+                .saturating_add(hand.development_cards[*card])
+                .saturating_add(hand.new_development_cards[*card]);
+        }
+        counted.knight = counted.knight.saturating_add(hand.knights);
+    }
+    let starting = DevelopmentCards::STARTING_DECK;
+    for card in DevelopmentCard::ALL.iter() {
+        // Played knights are kept in front of their owner and victory points are never played,
+        // so these two are exactly known. The other played cards are discarded
+        let coherent = match card {
+            DevelopmentCard::Knight | DevelopmentCard::VictoryPoint => counted[*card] == starting[*card],
+            _ => counted[*card] <= starting[*card],
+        };
+        if !coherent {
+            violations.push(Violation::DevelopmentCardCount { card: *card, counted: counted[*card] });
+        }
+    }
+}
+
+fn check_board(state: &State, violations: &mut Vec<Violation>) -> Result<(), BoardError> {
+    let player_count = state.player_count() as usize;
+    let layout = state.get_layout();
+    let mut settlements = vec![0u8; player_count];
+    let mut cities = vec![0u8; player_count];
+    let mut roads = vec![0u8; player_count];
+    // Buildings and distance rule
+    for intersection in layout.intersections.iter() {
+        if let Some((player, is_city)) = state.get_dynamic_intersection(*intersection)? {
+            if is_city {
+                cities[player.to_usize()] += 1;
+            } else {
+                settlements[player.to_usize()] += 1;
+            }
+            for neighbour in state.intersection_intersection_neighbours(*intersection)? {
+                // Each pair is only reported once
+                if neighbour < *intersection && state.get_dynamic_intersection(neighbour)?.is_some() {
+                    violations.push(Violation::DistanceRule { intersection: *intersection, neighbour });
+                }
+            }
+        }
+    }
+    // Roads and connectivity, marked by position in the layout's paths
+    let mut connected = vec![false; layout.paths.len()];
+    let mut queue = Vec::new();
+    for (position, path) in layout.paths.iter().enumerate() {
+        if let Some(player) = state.get_dynamic_path(*path)? {
+            roads[player.to_usize()] += 1;
+            for intersection in state.path_intersection_neighbours(*path)? {
+                if let Some((p, _)) = state.get_dynamic_intersection(intersection)? {
+                    if p == player && !connected[position] {
+                        connected[position] = true;
+                        queue.push(*path);
+                    }
+                }
+            }
+        }
+    }
+    // Spreads from the roads touching a building, going through any intersection
+    // since an opponent can settle in the middle of an existing road
+    while let Some(path) = queue.pop() {
+        let player = state.get_dynamic_path(path)?;
+        for neighbour in state.path_path_neighbours(path)? {
+            if let Some(position) = layout.position(neighbour) {
+                if !connected[position] && state.get_dynamic_path(neighbour)? == player {
+                    connected[position] = true;
+                    queue.push(neighbour);
+                }
+            }
+        }
+    }
+    for (position, path) in layout.paths.iter().enumerate() {
+        if let Some(player) = state.get_dynamic_path(*path)? {
+            if !connected[position] {
+                violations.push(Violation::DisconnectedRoad { player, path: *path });
+            }
+        }
+    }
+    // Piece counts
+    let starting = PlayerHand::new();
+    for (player, hand) in hands(state) {
+        let p = player.to_usize();
+        let pieces = [
+            (hand.road_pieces, roads[p], starting.road_pieces),
+            (hand.settlement_pieces, settlements[p], starting.settlement_pieces),
+            (hand.city_pieces, cities[p], starting.city_pieces),
+        ];
+        for (piece, (in_hand, on_board, total)) in pieces.iter().enumerate() {
+            if in_hand + on_board != *total {
+                violations.push(Violation::PieceCount {
+                    player,
+                    piece: piece as u8,
+                    in_hand: *in_hand,
+                    on_board: *on_board,
+                });
+            }
+        }
+        let expected = settlements[p] + 2 * cities[p];
+        if hand.building_vp != expected {
+            violations.push(Violation::BuildingVictoryPoints { player, recorded: hand.building_vp, expected });
+        }
+        if hand.continous_road > roads[p] {
+            violations.push(Violation::ContinousRoadTooLong { player, length: hand.continous_road, roads: roads[p] });
+        }
+    }
+    // Thief
+    let thief = state.get_thief_hex();
+    if let Hex::Water = state.get_static_hex(thief)? {
+        violations.push(Violation::ThiefNotOnLand { hex: thief });
+    }
+    Ok(())
+}
+
+fn check_victory_points(state: &State, phase: &Phase, violations: &mut Vec<Violation>) {
+    match phase {
+        Phase::FinishedGame { winner } => {
+            if winner.to_u8() < state.player_count() {
+                let vp = state.get_player_total_vp(*winner);
+                if vp < 10 {
+                    violations.push(Violation::UndeservedWinner { player: *winner, vp });
+                }
+            }
+        }
+        _ => {
+            for (player, _) in hands(state) {
+                let vp = state.get_player_total_vp(player);
+                if vp >= 10 {
+                    violations.push(Violation::UndeclaredWinner { player, vp });
+                }
+            }
+        }
+    }
+}
+
+/// Checks that the holder of a title is the one it should be
+///
+/// The holder must reach the minimum and not be beaten by anyone.
+/// Without holder, nobody can be alone at the top above the minimum
+fn check_title(holder: Option<PlayerId>, values: &[u8], minimum: u8) -> bool {
+    let best = values.iter().cloned().max().unwrap_or(0);
+    match holder {
+        Some(holder) => values[holder.to_usize()] >= minimum && values[holder.to_usize()] == best,
+        None => best < minimum || values.iter().filter(|v| **v == best).count() > 1,
+    }
+}
+
+fn check_longest_road(state: &State, violations: &mut Vec<Violation>) {
+    let holder = state.get_longest_road().map(|(player, _)| player);
+    let lengths: Vec<u8> = hands(state).map(|(_, hand)| hand.continous_road).collect();
+    if !check_title(holder, &lengths, 5) {
+        violations.push(Violation::LongestRoad { holder });
+    }
+}
+
+fn check_largest_army(state: &State, violations: &mut Vec<Violation>) {
+    let holder = state.get_largest_army().map(|(player, _)| player);
+    let knights: Vec<u8> = hands(state).map(|(_, hand)| hand.knights).collect();
+    if !check_title(holder, &knights, 3) {
+        violations.push(Violation::LargestArmy { holder });
+    }
+}
+
+fn check_discards(state: &State, phase: &Phase, violations: &mut Vec<Violation>) {
+    let discards = state.peek_discards();
+    let discarding = match phase {
+        Phase::Turn { player: _, turn_phase: TurnPhase::Discard(player), development_phase: _ } => *player,
+        _ => {
+            // Outside of a discard phase, nothing should be held
+            for (player, _) in discards.iter() {
+                violations.push(Violation::Discards { player: *player });
+            }
+            return;
+        }
+    };
+    if !discards.iter().any(|(player, _)| *player == discarding) {
+        violations.push(Violation::Discards { player: discarding });
+    }
+    // Players before the discarding one already chose, the others didn't
+    let mut before = true;
+    for (player, discard) in discards.iter() {
+        if *player == discarding {
+            before = false;
+        }
+        let hand = state.get_player_hand(*player).resources;
+        let coherent = match discard {
+            Some(discarded) => before && hand >= *discarded && discarded.total() == hand.total() / 2,
+            None => !before && hand.total() >= 7,
+        };
+        if !coherent {
+            violations.push(Violation::Discards { player: *player });
+        }
+    }
+}
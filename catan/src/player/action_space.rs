@@ -0,0 +1,201 @@
+use serde::Serialize;
+
+use crate::game::Action;
+use crate::state::PlayerId;
+use crate::board::Layout;
+use crate::utils::{Coord, Resource, Resources};
+
+/// Version of the enumeration of [ActionSpace], changed whenever an index could map to another action
+pub const ACTION_SPACE_VERSION: u32 = 1;
+
+/// Consecutive indices of an [ActionSpace] holding one kind of actions
+#[derive(Copy, Clone, PartialEq, Debug, Serialize)]
+pub struct ActionSection {
+    pub name: &'static str,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl ActionSection {
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.start <= index && index < self.end()
+    }
+}
+
+/// Mapping between indices and every action a seat could play
+///
+/// Coords are taken in increasing order, whatever the order of the layout.
+/// Sections come in this order:
+/// - `move_thief`: for each hex of the layout, one action per victim, starting with the seat itself
+///   and then the next seats, so that victims are relative to the seat
+/// - `build_road`: one action per path of the layout
+/// - `build_settlement_city`: for each intersection of the layout, a settlement then a city
+/// - `turn_phase`: rolling the dice then ending the turn
+/// - `trade_bank`: each given resource, then each asked one
+/// - `development`: buying and playing cards, choosing free resources then monopolies
+/// - `keep`: every hand of four cards kept when discarding
+#[derive(Clone)]
+pub struct ActionSpace {
+    hexes: Vec<Coord>,
+    paths: Vec<Coord>,
+    intersections: Vec<Coord>,
+    player: PlayerId,
+    player_count: u8,
+    actions: Vec<Action>,
+    sections: Vec<ActionSection>,
+}
+
+impl ActionSpace {
+    pub fn new(layout: &Layout, player_count: u8, player: PlayerId) -> ActionSpace {
+        let sorted = |coords: &[Coord]| {
+            let mut coords = coords.to_vec();
+            coords.sort();
+            coords
+        };
+        let hexes = sorted(&layout.hexes);
+        let paths = sorted(&layout.paths);
+        let intersections = sorted(&layout.intersections);
+        let mut actions = Vec::new();
+        let mut sections = Vec::new();
+        let mut section = |name: &'static str, section_actions: Vec<Action>| {
+            sections.push(ActionSection { name, start: actions.len(), len: section_actions.len() });
+            actions.extend(section_actions);
+        };
+        section("move_thief", hexes.iter().flat_map(|hex| {
+            (0..player_count).map(move |p| Action::MoveThief {
+                hex: *hex,
+                victim: PlayerId::from((p + player.to_u8()) % player_count),
+            })
+        }).collect());
+        section("build_road", paths.iter().map(|path| Action::BuildRoad { path: *path }).collect());
+        section("build_settlement_city", intersections.iter().flat_map(|intersection| {
+            vec![Action::BuildSettlement { intersection: *intersection }, Action::BuildCity { intersection: *intersection }]
+        }).collect());
+        section("turn_phase", vec![Action::RollDice, Action::EndTurn]);
+        section("trade_bank", Resource::ALL.iter().flat_map(|given| {
+            Resource::ALL.iter()
+                .filter(move |asked| *asked != given)
+                .map(move |asked| Action::TradeBank { given: *given, asked: *asked })
+        }).collect());
+        let mut development = vec![
+            Action::BuyDevelopment,
+            Action::DevelopmentKnight,
+            Action::DevelopmentRoadBuilding,
+            Action::DevelopmentYearOfPlenty,
+        ];
+        development.extend(Resource::ALL.iter().map(|resource| Action::ChooseFreeResource { resource: *resource }));
+        development.extend(Resource::ALL.iter().map(|resource| Action::DevelopmentMonopole { resource: *resource }));
+        section("development", development);
+        let mut keep = Vec::new();
+        for b in 0..5 {
+            for l in 0..5-b {
+                for o in 0..5-(b+l) {
+                    for g in 0..5-(b+l+o) {
+                        let resources = Resources::new(b, l, o, g, 4-(b+l+o+g));
+                        keep.push(Action::Keep { resources });
+                    }
+                }
+            }
+        }
+        section("keep", keep);
+        ActionSpace {
+            hexes,
+            paths,
+            intersections,
+            player,
+            player_count,
+            actions,
+            sections,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        ACTION_SPACE_VERSION
+    }
+
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Every action, in the order of their indices
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    pub fn sections(&self) -> &[ActionSection] {
+        &self.sections
+    }
+
+    pub fn section(&self, name: &str) -> Option<ActionSection> {
+        self.sections.iter().find(|section| section.name == name).copied()
+    }
+
+    pub fn action(&self, index: usize) -> Option<Action> {
+        self.actions.get(index).copied()
+    }
+
+    pub fn index(&self, action: Action) -> Option<usize> {
+        let section = |name| self.section(name).expect("Every section exists");
+        let position = |coords: &[Coord], coord| coords.binary_search(&coord).ok();
+        let index = match action {
+            Action::MoveThief { hex, victim } => {
+                if victim.to_u8() >= self.player_count {
+                    return None;
+                }
+                let offset = (victim.to_u8() + self.player_count - self.player.to_u8()) % self.player_count;
+                section("move_thief").start + position(&self.hexes, hex)? * self.player_count as usize + offset as usize
+            }
+            Action::BuildRoad { path } => section("build_road").start + position(&self.paths, path)?,
+            Action::BuildSettlement { intersection } => section("build_settlement_city").start + 2 * position(&self.intersections, intersection)?,
+            Action::BuildCity { intersection } => section("build_settlement_city").start + 2 * position(&self.intersections, intersection)? + 1,
+            Action::Exit => return None,
+            _ => {
+                let name = match action {
+                    Action::RollDice | Action::EndTurn => "turn_phase",
+                    Action::TradeBank { .. } => "trade_bank",
+                    Action::Keep { .. } => "keep",
+                    _ => "development",
+                };
+                let section = section(name);
+                return self.actions[section.start..section.end()].iter()
+                    .position(|other| *other == action)
+                    .map(|position| section.start + position);
+            }
+        };
+        Some(index)
+    }
+
+    /// Human readable name of the action at an index, such as `BuildRoad (1,0)` or `MoveThief (0,2) +1`
+    pub fn name(&self, index: usize) -> Option<String> {
+        let relative = |victim: PlayerId| (victim.to_u8() + self.player_count - self.player.to_u8()) % self.player_count;
+        Some(match self.action(index)? {
+            Action::MoveThief { hex, victim } => format!("MoveThief {} +{}", hex, relative(victim)),
+            Action::BuildRoad { path } => format!("BuildRoad {}", path),
+            Action::BuildSettlement { intersection } => format!("BuildSettlement {}", intersection),
+            Action::BuildCity { intersection } => format!("BuildCity {}", intersection),
+            Action::TradeBank { given, asked } => format!("TradeBank {} {}", given, asked),
+            Action::ChooseFreeResource { resource } => format!("ChooseFreeResource {}", resource),
+            Action::DevelopmentMonopole { resource } => format!("DevelopmentMonopole {}", resource),
+            Action::Keep { resources } => {
+                let cards: String = Resource::ALL.iter()
+                    .map(|resource| resource.to_string().repeat(resources[*resource].max(0) as usize))
+                    .collect();
+                format!("Keep {}", cards)
+            }
+            action => format!("{:?}", action),
+        })
+    }
+
+    /// Index of the action with the given [name](ActionSpace::name)
+    pub fn index_of_name(&self, name: &str) -> Option<usize> {
+        (0..self.len()).find(|index| self.name(*index).as_deref() == Some(name))
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Serialize, Deserialize};
+
+use crate::state::{StateTrait, PlayerId};
+use crate::board::analysis::intersection_value;
+use crate::board::reach::RoadDistances;
+use crate::utils::{Harbor, Resource};
+
+/// Roads a settlement site can be away from a network to count as expansion room
+pub const EXPANSION_DISTANCE: u8 = 2;
+
+/// Names of the features, in the order of [to_vec](Evaluation::to_vec)
+pub const FEATURES: [&str; 12] = [
+    "victory_points",
+    "production_brick",
+    "production_lumber",
+    "production_ore",
+    "production_grain",
+    "production_wool",
+    "diversity",
+    "harbors",
+    "development_cards",
+    "longest_road",
+    "largest_army",
+    "expansion",
+];
+
+/// Features of a position, from the point of view of one player
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize)]
+pub struct Evaluation {
+    /// Total victory points, hidden ones included
+    pub victory_points: f32,
+    /// Pips of each resource around the buildings of the player, cities counting twice
+    pub production: [f32; Resource::COUNT],
+    /// Number of different resources produced
+    pub diversity: f32,
+    /// Accessible harbors, special ones only counting when their resource is produced
+    pub harbors: f32,
+    /// Development cards held, victory points excluded
+    pub development_cards: f32,
+    /// 1 for the holder, otherwise how close the player's longest road is to taking it
+    pub longest_road: f32,
+    /// 1 for the holder, otherwise how close the player's army is to taking it
+    pub largest_army: f32,
+    /// Legal settlement sites at most [EXPANSION_DISTANCE] roads away
+    pub expansion: f32,
+}
+
+/// Weights of each feature of an [Evaluation]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvaluationWeights {
+    pub victory_points: f32,
+    pub production: [f32; Resource::COUNT],
+    pub diversity: f32,
+    pub harbors: f32,
+    pub development_cards: f32,
+    pub longest_road: f32,
+    pub largest_army: f32,
+    pub expansion: f32,
+}
+
+impl Default for EvaluationWeights {
+    /// Roughly a victory point for a settlement on 12 pips, ore and grain being worth a bit more for cities
+    fn default() -> Self {
+        EvaluationWeights {
+            victory_points: 1.0,
+            production: [0.06, 0.06, 0.08, 0.08, 0.05],
+            diversity: 0.2,
+            harbors: 0.15,
+            development_cards: 0.3,
+            longest_road: 0.5,
+            largest_army: 0.5,
+            expansion: 0.1,
+        }
+    }
+}
+
+impl EvaluationWeights {
+    /// Weights in the order of [FEATURES]
+    pub fn to_vec(&self) -> Vec<f32> {
+        let mut weights = vec![self.victory_points];
+        weights.extend(self.production.iter());
+        weights.extend([self.diversity, self.harbors, self.development_cards, self.longest_road, self.largest_army, self.expansion].iter());
+        weights
+    }
+
+    /// Inverse of [to_vec](EvaluationWeights::to_vec), `None` if there isn't one weight per feature
+    pub fn from_vec(weights: &[f32]) -> Option<EvaluationWeights> {
+        if weights.len() != FEATURES.len() {
+            return None;
+        }
+        let mut production = [0.0; Resource::COUNT];
+        production.copy_from_slice(&weights[1..6]);
+        Some(EvaluationWeights {
+            victory_points: weights[0],
+            production,
+            diversity: weights[6],
+            harbors: weights[7],
+            development_cards: weights[8],
+            longest_road: weights[9],
+            largest_army: weights[10],
+            expansion: weights[11],
+        })
+    }
+}
+
+impl Evaluation {
+    pub fn new(state: &dyn StateTrait, player: PlayerId) -> Evaluation {
+        let hand = state.get_player_hand(player);
+        let mut production = [0.0; Resource::COUNT];
+        for intersection in state.get_layout().intersections.iter() {
+            if let Ok(Some((owner, is_city))) = state.get_dynamic_intersection(*intersection) {
+                if owner == player {
+                    let value = intersection_value(state, *intersection);
+                    for (total, pips) in production.iter_mut().zip(value.production.iter()) {
+                        *total += *pips as f32 * if is_city { 2.0 } else { 1.0 };
+                    }
+                }
+            }
+        }
+        let mut harbors = if hand.harbor[Harbor::Generic] { 1.0 } else { 0.0 };
+        for resource in Resource::ALL.iter() {
+            if hand.harbor[Harbor::Special(*resource)] && production[resource.to_usize()] > 0.0 {
+                harbors += 1.0;
+            }
+        }
+        let cards = hand.development_cards;
+        let new_cards = hand.new_development_cards;
+        let development_cards = (cards.total() - cards.victory_point + new_cards.total() - new_cards.victory_point) as f32;
+        let distances = RoadDistances::new(state, player);
+        let expansion = distances.settlement_sites(state).iter()
+            .filter(|(_, distance)| *distance <= EXPANSION_DISTANCE)
+            .count() as f32;
+        Evaluation {
+            victory_points: state.get_player_total_vp(player) as f32,
+            production,
+            diversity: production.iter().filter(|pips| **pips > 0.0).count() as f32,
+            harbors,
+            development_cards,
+            longest_road: proximity(state.get_longest_road(), player, hand.continous_road, 5),
+            largest_army: proximity(state.get_largest_army(), player, hand.knights, 3),
+            expansion,
+        }
+    }
+
+    /// Features in the order of [FEATURES]
+    pub fn to_vec(&self) -> Vec<f32> {
+        let mut features = vec![self.victory_points];
+        features.extend(self.production.iter());
+        features.extend([self.diversity, self.harbors, self.development_cards, self.longest_road, self.largest_army, self.expansion].iter());
+        features
+    }
+
+    pub fn score(&self, weights: &EvaluationWeights) -> f32 {
+        self.to_vec().iter().zip(weights.to_vec().iter()).map(|(feature, weight)| feature * weight).sum()
+    }
+}
+
+/// How close a player is to a title, 1 if they hold it
+fn proximity(holder: Option<(PlayerId, u8)>, player: PlayerId, length: u8, minimum: u8) -> f32 {
+    match holder {
+        Some((holder, _)) if holder == player => 1.0,
+        Some((_, held)) => (length as f32 / (held + 1) as f32).min(1.0),
+        None => (length as f32 / minimum as f32).min(1.0),
+    }
+}
+
+/// How good the position is for the player
+pub fn evaluate(state: &dyn StateTrait, player: PlayerId, weights: &EvaluationWeights) -> f32 {
+    Evaluation::new(state, player).score(weights)
+}
+
+/// How much better the position is for the player than for its best opponent
+pub fn evaluate_relative(state: &dyn StateTrait, player: PlayerId, weights: &EvaluationWeights) -> f32 {
+    let best_opponent = (0..state.player_count())
+        .map(PlayerId::from)
+        .filter(|p| *p != player)
+        .map(|p| evaluate(state, p, weights))
+        .fold(f32::NEG_INFINITY, f32::max);
+    let own = evaluate(state, player, weights);
+    if best_opponent.is_finite() { own - best_opponent } else { own }
+}
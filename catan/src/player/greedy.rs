@@ -0,0 +1,228 @@
+use crate::game::{Phase, Action, Error, Notification};
+use crate::state::{PlayerView, PlayerId};
+use crate::board::analysis::intersection_value;
+use crate::board::reach::RoadDistances;
+use crate::board::opening::{rank_openings, opening_road, unconnected_settlement, OpeningWeights};
+use crate::board::utils::topology::Topology;
+use crate::utils::{Coord, Resource, Resources, Harbor};
+use super::{CatanPlayer, ActionPickerPlayer, PickerPlayerTrait};
+
+/// Rule based player, taking whatever looks best right now
+///
+/// Opens following [rank_openings], builds cities first, then settlements,
+/// development cards and roads, trades with the bank toward its next build,
+/// and sends the thief on the leader
+#[derive(Default)]
+pub struct Greedy {}
+
+/// Priority of the kind of action, then how good the action is among its kind
+type Score = (i32, i32);
+
+/// What a turn is spent on
+struct Plan {
+    /// Cost of the next build
+    goal: Resources,
+    /// First road toward each reachable settlement site, with the value of the site
+    roads: Vec<(Coord, i32)>,
+    /// Value of each settlement site during the initial placement
+    openings: Vec<(Coord, i32)>,
+    /// Road to place during the initial placement
+    opening_road: Option<Coord>,
+}
+
+impl PickerPlayerTrait for Greedy {
+    type ACTIONS = Vec<Action>;
+    type PICKED = Action;
+
+    fn new_game(&mut self, _: PlayerId, _: &Phase, _: &PlayerView, _: &Vec<Action>) {}
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView, legal_actions: &Vec<Action>) -> Action {
+        let plan = self.plan(phase, view);
+        // Reversed so that the first of equally good actions is picked
+        *legal_actions.iter().rev()
+            .max_by_key(|action| self.score(phase, view, &plan, **action))
+            .expect("There is always a legal action")
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        println!("{:?}", error);
+    }
+
+    fn notify(&mut self, _: &Notification) {}
+
+    fn results(&mut self, _: &PlayerView, _:PlayerId) {}
+}
+
+impl Greedy {
+    pub fn new() -> Greedy {
+        Greedy {}
+    }
+
+    pub fn new_player() -> impl CatanPlayer {
+        ActionPickerPlayer::new(Greedy::new())
+    }
+
+    fn plan(&self, phase: &Phase, view: &PlayerView) -> Plan {
+        let hand = view.get_hand();
+        let distances = RoadDistances::new(view, view.player());
+        let sites = distances.settlement_sites(view);
+        let mut roads = Vec::new();
+        if hand.settlement_pieces > 0 {
+            for (site, distance) in sites.iter().cloned() {
+                if distance == 0 || distance as usize > hand.road_pieces as usize {
+                    continue;
+                }
+                if let Some(road) = distances.road_plan(site).and_then(|plan| plan.first().cloned()) {
+                    roads.push((road, self.site_value(view, site) - 20 * distance as i32));
+                }
+            }
+        }
+        let mut candidates = Vec::new();
+        if hand.city_pieces > 0 && hand.settlement_pieces < 5 {
+            candidates.push(Resources::CITY);
+        }
+        if hand.settlement_pieces > 0 && sites.iter().any(|(_, distance)| *distance == 0) {
+            candidates.push(Resources::SETTLEMENT);
+        }
+        if view.get_development_deck_size() > 0 {
+            candidates.push(Resources::DVP_CARD);
+        }
+        if !roads.is_empty() {
+            candidates.push(Resources::ROAD);
+        }
+        // Closest build, the first in priority order when tied
+        let goal = candidates.iter().cloned()
+            .min_by_key(|cost| missing(hand.resources, *cost))
+            .unwrap_or(Resources::ROAD);
+        let mut openings = Vec::new();
+        let mut placement_road = None;
+        if let Phase::InitialPlacement { placing_road, .. } = phase {
+            let weights = OpeningWeights::default();
+            if *placing_road {
+                placement_road = unconnected_settlement(view, view.player())
+                    .and_then(|settlement| opening_road(view, view.player(), settlement, &weights));
+            } else {
+                // Candidates are sorted, so the first one of each settlement has its best road
+                for candidate in rank_openings(view, view.player(), view.player_count(), &weights) {
+                    if !openings.iter().any(|(settlement, _)| *settlement == candidate.settlement) {
+                        openings.push((candidate.settlement, (100.0 * candidate.value) as i32));
+                    }
+                }
+            }
+        }
+        Plan {
+            goal,
+            roads,
+            openings,
+            opening_road: placement_road,
+        }
+    }
+
+    fn score(&self, phase: &Phase, view: &PlayerView, plan: &Plan, action: Action) -> Score {
+        let hand = view.get_hand();
+        let placing = matches!(phase, Phase::InitialPlacement { .. });
+        match action {
+            Action::BuildSettlement { intersection } if placing => match plan.openings.iter().find(|(site, _)| *site == intersection) {
+                Some((_, value)) => (50, *value),
+                None => (0, 0),
+            },
+            Action::BuildSettlement { intersection } => (50, self.site_value(view, intersection)),
+            Action::BuildRoad { path } if placing => (0, if plan.opening_road == Some(path) { 1 } else { 0 }),
+            Action::BuildRoad { path } => match plan.roads.iter().find(|(road, _)| *road == path) {
+                Some((_, value)) => (20, *value),
+                // Only built when a road building card forces it
+                None => (0, 0),
+            },
+            Action::BuildCity { intersection } => (60, intersection_value(view, intersection).pips as i32),
+            Action::BuyDevelopment => (40, 0),
+            Action::RollDice => (50, 0),
+            Action::EndTurn => (10, 0),
+            Action::DevelopmentKnight if self.thief_blocks(view) => (70, 0),
+            Action::DevelopmentKnight => (30, 0),
+            Action::DevelopmentRoadBuilding if plan.roads.is_empty() || hand.road_pieces < 2 => (0, 0),
+            Action::DevelopmentRoadBuilding => (35, 0),
+            Action::DevelopmentYearOfPlenty if missing(hand.resources, plan.goal) == 0 => (0, 0),
+            Action::DevelopmentYearOfPlenty => (35, 0),
+            Action::DevelopmentMonopole { resource } => {
+                let needed = (plan.goal[resource] - hand.resources[resource]) as i32;
+                if needed > 0 { (33, needed) } else { (0, 0) }
+            }
+            Action::ChooseFreeResource { resource } => (0, (plan.goal[resource] - hand.resources[resource]) as i32),
+            Action::TradeBank { given, asked } => {
+                let rate = hand.harbor.rate(given) as i8;
+                let surplus = hand.resources[given] - plan.goal[given];
+                if surplus >= rate && hand.resources[asked] < plan.goal[asked] {
+                    (25, -(rate as i32))
+                } else {
+                    (0, 0)
+                }
+            }
+            Action::MoveThief { hex, victim } => (0, self.thief_value(view, hex, victim)),
+            Action::Keep { resources } => (0, -100 * missing(resources, plan.goal) as i32 + resources.total() as i32),
+            Action::Exit => (-1, 0),
+        }
+    }
+
+    /// Production and diversity of a settlement, counting resources the player doesn't have yet twice
+    fn site_value(&self, view: &PlayerView, intersection: Coord) -> i32 {
+        let value = intersection_value(view, intersection);
+        let produced = self.production(view);
+        let new_resources = Resource::ALL.iter()
+            .filter(|resource| value.production[resource.to_usize()] > 0 && produced[resource.to_usize()] == 0)
+            .count() as i32;
+        let harbor = if value.harbor == Harbor::None { 0 } else { 3 };
+        10 * value.pips as i32 + 6 * value.diversity as i32 + 8 * new_resources + harbor
+    }
+
+    /// Pips of each resource around the buildings of the player
+    fn production(&self, view: &PlayerView) -> [u8; Resource::COUNT] {
+        let mut production = [0; Resource::COUNT];
+        for intersection in view.get_layout().intersections.iter() {
+            if let Ok(Some((owner, is_city))) = view.get_dynamic_intersection(*intersection) {
+                if owner == view.player() {
+                    let value = intersection_value(view, *intersection);
+                    for (total, pips) in production.iter_mut().zip(value.production.iter()) {
+                        *total += pips * if is_city { 2 } else { 1 };
+                    }
+                }
+            }
+        }
+        production
+    }
+
+    /// Is the thief on a productive hex next to a building of the player
+    fn thief_blocks(&self, view: &PlayerView) -> bool {
+        let hex = view.get_thief_hex();
+        view.get_static_hex(hex).map(|tile| tile.pips() > 0).unwrap_or(false)
+            && self.buildings_around(view, hex).iter().any(|(owner, _)| *owner == view.player())
+    }
+
+    fn buildings_around(&self, view: &PlayerView, hex: Coord) -> Vec<(PlayerId, bool)> {
+        view.get_layout().hex_intersection_neighbours(hex).unwrap_or_default().into_iter()
+            .filter_map(|intersection| view.get_dynamic_intersection(intersection).unwrap_or(None))
+            .collect()
+    }
+
+    /// Production taken from opponents, mostly from the leader, and never from the player
+    fn thief_value(&self, view: &PlayerView, hex: Coord, victim: PlayerId) -> i32 {
+        let pips = view.get_static_hex(hex).map(|tile| tile.pips()).unwrap_or(0) as i32;
+        let mut value = 0;
+        for (owner, is_city) in self.buildings_around(view, hex) {
+            if owner == view.player() {
+                return -1000;
+            }
+            let vp = view.get_player_public_vp(owner) as i32;
+            value += pips * (1 + vp) * if is_city { 2 } else { 1 };
+        }
+        if victim != view.player() && victim != PlayerId::NONE {
+            let public = view.get_public_hand(victim);
+            value += 10 * view.get_player_public_vp(victim) as i32 + public.resource_count as i32;
+        }
+        value
+    }
+}
+
+/// Number of cards still needed to afford a cost
+fn missing(resources: Resources, cost: Resources) -> i8 {
+    Resource::ALL.iter().map(|resource| (cost[*resource] - resources[*resource]).max(0)).sum()
+}
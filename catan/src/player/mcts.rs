@@ -0,0 +1,454 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use rand::{Rng, SeedableRng};
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+
+use crate::game::{Phase, TurnPhase, Action, Error, Notification, legal, apply};
+use crate::state::{State, StateTrait, TricellState, PlayerView, PlayerId, PlayerHand};
+use crate::utils::{Resource, Resources, DevelopmentDeck, DevelopmentCards};
+use super::{CatanPlayer, ActionPickerPlayer, PickerPlayerTrait, HandTracker, Greedy};
+
+/// How actions are picked once a search leaves its tree
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RolloutPolicy {
+    Random,
+    /// Plays like the [Greedy] player, slower but closer to real games
+    Greedy,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MctsConfig {
+    /// Number of searches per decision, shared between the threads
+    pub iterations: u32,
+    /// The search stops after this duration, even if iterations are left
+    pub time_limit: Option<Duration>,
+    /// Exploration constant of the upper confidence bound
+    pub exploration: f32,
+    pub rollout: RolloutPolicy,
+    /// Rollouts still running after this many actions are scored with victory points
+    pub max_rollout_actions: u32,
+    /// Independent trees searched in parallel, whose root visits are summed
+    pub threads: usize,
+    /// Seed of the searches, random if `None`
+    pub seed: Option<u64>,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            iterations: 1000,
+            time_limit: None,
+            exploration: 0.7,
+            rollout: RolloutPolicy::Random,
+            max_rollout_actions: 200,
+            threads: 1,
+            seed: None,
+        }
+    }
+}
+
+/// Attempts at sampling opponent hands that add up with the bank to the starting bank
+const SAMPLE_ATTEMPTS: usize = 50;
+
+/// What a seat knows about a state, from which the hidden information can be sampled
+///
+/// Opponent hands are drawn from the hands a [HandTracker] still considers possible,
+/// and their development cards and the order of the deck from the unseen cards
+pub struct InformationSet {
+    player: PlayerId,
+    phase: Phase,
+    /// Public information and the hand of the seat, opponents have no resources nor development cards
+    known: TricellState,
+    resource_counts: Vec<i8>,
+    possible_hands: Vec<Vec<Resources>>,
+    development_card_counts: Vec<u8>,
+    /// Development cards in the deck or in an opponent's hand
+    unseen_development_cards: DevelopmentCards,
+}
+
+impl InformationSet {
+    pub fn new(view: &PlayerView, tracker: &HandTracker, phase: &Phase) -> InformationSet {
+        static ERROR_MESSAGE: &str = "View and copied state should share their layout";
+        let layout = view.get_layout();
+        let player_count = view.player_count();
+        let mut known = TricellState::new(Arc::new(layout.clone()), player_count as usize);
+        for hex in layout.hexes.iter() {
+            known.set_static_hex(*hex, view.get_static_hex(*hex).expect(ERROR_MESSAGE)).expect(ERROR_MESSAGE);
+        }
+        for path in layout.paths.iter() {
+            let owner = view.get_dynamic_path(*path).expect(ERROR_MESSAGE).unwrap_or(PlayerId::NONE);
+            known.set_dynamic_path(*path, owner).expect(ERROR_MESSAGE);
+        }
+        for intersection in layout.intersections.iter() {
+            known.set_static_harbor(*intersection, view.get_static_harbor(*intersection).expect(ERROR_MESSAGE)).expect(ERROR_MESSAGE);
+            let (owner, is_city) = view.get_dynamic_intersection(*intersection).expect(ERROR_MESSAGE).unwrap_or((PlayerId::NONE, false));
+            known.set_dynamic_intersection(*intersection, owner, is_city).expect(ERROR_MESSAGE);
+        }
+        known.set_thief_hex(view.get_thief_hex());
+        *known.get_bank_resources_mut() = view.get_bank_resources();
+        let mut resource_counts = Vec::new();
+        let mut possible_hands = Vec::new();
+        let mut development_card_counts = Vec::new();
+        for p in 0..player_count {
+            let p = PlayerId::from(p);
+            let public = view.get_public_hand(p);
+            let hand = known.get_player_hand_mut(p);
+            if p == view.player() {
+                *hand = *view.get_hand();
+            } else {
+                *hand = PlayerHand {
+                    road_pieces: public.road_pieces,
+                    settlement_pieces: public.settlement_pieces,
+                    city_pieces: public.city_pieces,
+                    building_vp: public.building_vp,
+                    knights: public.knights,
+                    continous_road: public.continous_road,
+                    harbor: public.harbor,
+                    ..PlayerHand::new()
+                };
+            }
+            resource_counts.push(public.resource_count);
+            possible_hands.push(tracker.possible_hands(p).to_vec());
+            development_card_counts.push(tracker.development_card_count(p));
+        }
+        known.set_longest_road(view.get_longest_road().map(|(holder, _)| holder));
+        known.set_largest_army(view.get_largest_army().map(|(holder, _)| holder));
+        let mut own_cards = view.get_hand().development_cards;
+        own_cards += view.get_hand().new_development_cards;
+        InformationSet {
+            player: view.player(),
+            phase: *phase,
+            known,
+            resource_counts,
+            possible_hands,
+            development_card_counts,
+            unseen_development_cards: tracker.unseen_development_cards(own_cards),
+        }
+    }
+
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    /// A full state coherent with what the seat knows, and the phase it is in
+    pub fn sample<R : Rng>(&self, rng: &mut R) -> (State, Phase) {
+        let mut state: State = Box::new(self.known.clone());
+        for (p, resources) in self.sample_hands(rng).into_iter().enumerate() {
+            state.get_player_hand_mut(PlayerId::from(p)).resources = resources;
+        }
+        let mut unseen = DevelopmentDeck::OnDemand(self.unseen_development_cards);
+        for (p, count) in self.development_card_counts.iter().enumerate() {
+            let p = PlayerId::from(p);
+            if p == self.player {
+                continue;
+            }
+            for _ in 0..*count {
+                if let Some(card) = unseen.draw(rng) {
+                    state.get_player_hand_mut(p).development_cards[card] += 1;
+                }
+            }
+        }
+        *state.get_development_deck_mut() = DevelopmentDeck::shuffled(unseen.cards(), rng);
+        // Players before the seat in the discard order already picked their discards
+        if let Phase::Turn { turn_phase: TurnPhase::Discard(discarding), .. } = self.phase {
+            let mut discards = Vec::new();
+            let mut decided = true;
+            for p in 0..state.player_count() {
+                let p = PlayerId::from(p);
+                let resources = state.get_player_hand(p).resources;
+                if resources.total() < 7 {
+                    continue;
+                }
+                decided &= p != discarding;
+                let discard = if decided { Some(random_cards(resources, resources.total() / 2, rng)) } else { None };
+                discards.push((p, discard));
+            }
+            state.hold_discards(discards);
+        }
+        (state, self.phase)
+    }
+
+    fn sample_hands<R : Rng>(&self, rng: &mut R) -> Vec<Resources> {
+        let own = self.known.get_player_hand(self.player).resources;
+        let hidden = Resources::STARTING_BANK - self.known.get_bank_resources() - own;
+        let mut hands = vec![Resources::ZERO; self.resource_counts.len()];
+        hands[self.player.to_usize()] = own;
+        for _ in 0..SAMPLE_ATTEMPTS {
+            let mut remaining = hidden;
+            let mut coherent = true;
+            for (p, possible) in self.possible_hands.iter().enumerate() {
+                if p == self.player.to_usize() {
+                    continue;
+                }
+                let candidates: Vec<&Resources> = possible.iter().filter(|hand| **hand <= remaining).collect();
+                hands[p] = match candidates.choose(rng) {
+                    Some(hand) => **hand,
+                    None => {
+                        coherent = false;
+                        random_cards(remaining, self.resource_counts[p], rng)
+                    }
+                };
+                remaining -= hands[p];
+            }
+            if coherent && remaining == Resources::ZERO {
+                break;
+            }
+        }
+        hands
+    }
+}
+
+/// Random cards taken from a pool, ignoring its negative counts
+fn random_cards<R : Rng>(mut pool: Resources, count: i8, rng: &mut R) -> Resources {
+    for resource in Resource::ALL.iter() {
+        pool[*resource] = pool[*resource].max(0);
+    }
+    let mut picked = Resources::ZERO;
+    for _ in 0..count.min(pool.total()) {
+        let mut index = rng.gen_range(0, pool.total());
+        for resource in Resource::ALL.iter() {
+            if index < pool[*resource] {
+                pool[*resource] -= 1;
+                picked[*resource] += 1;
+                break;
+            }
+            index -= pool[*resource];
+        }
+    }
+    picked
+}
+
+struct Node {
+    /// Action leading to this node, `None` for the root
+    action: Option<Action>,
+    /// Player who played the action
+    player: PlayerId,
+    children: Vec<usize>,
+    visits: u32,
+    /// Number of times the action was legal when its parent was visited
+    availability: u32,
+    reward: f32,
+}
+
+impl Node {
+    fn new(action: Option<Action>, player: PlayerId) -> Node {
+        Node {
+            action,
+            player,
+            children: Vec::new(),
+            visits: 0,
+            availability: 1,
+            reward: 0.0,
+        }
+    }
+}
+
+/// One tree of an information set Monte Carlo tree search
+///
+/// Every iteration samples a new determinization, so only the actions legal in it are considered,
+/// and the exploration term uses how often an action was available instead of the parent visits
+struct Search<'a> {
+    config: MctsConfig,
+    info: &'a InformationSet,
+    nodes: Vec<Node>,
+}
+
+impl<'a> Search<'a> {
+    fn new(config: MctsConfig, info: &'a InformationSet) -> Search<'a> {
+        Search {
+            config,
+            info,
+            nodes: vec![Node::new(None, PlayerId::NONE)],
+        }
+    }
+
+    fn iterate<R : Rng>(&mut self, rng: &mut R) {
+        let (mut state, mut phase) = self.info.sample(rng);
+        let mut path = vec![0];
+        let mut node = 0;
+        // Selection and expansion
+        while !matches!(phase, Phase::FinishedGame { .. }) {
+            let legal = legal::legal_actions(&phase, &state);
+            let mut available = Vec::new();
+            for child in self.nodes[node].children.iter() {
+                if legal.contains(&self.nodes[*child].action.unwrap()) {
+                    available.push(*child);
+                }
+            }
+            let untried: Vec<Action> = legal.iter()
+                .filter(|action| available.iter().all(|child| self.nodes[*child].action != Some(**action)))
+                .cloned()
+                .collect();
+            for child in available.iter() {
+                self.nodes[*child].availability += 1;
+            }
+            let next = if let Some(action) = untried.choose(rng) {
+                self.nodes.push(Node::new(Some(*action), phase.player()));
+                let child = self.nodes.len() - 1;
+                self.nodes[node].children.push(child);
+                child
+            } else {
+                match available.iter().max_by(|a, b| self.ucb(**a).partial_cmp(&self.ucb(**b)).unwrap()) {
+                    Some(child) => *child,
+                    None => break,
+                }
+            };
+            apply(&mut phase, &mut state, self.nodes[next].action.unwrap(), rng);
+            path.push(next);
+            node = next;
+            if self.nodes[next].visits == 0 {
+                break;
+            }
+        }
+        let rewards = self.rollout(&mut state, &mut phase, rng);
+        // Backpropagation
+        for index in path {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            if node.player != PlayerId::NONE {
+                node.reward += rewards[node.player.to_usize()];
+            }
+        }
+    }
+
+    fn ucb(&self, index: usize) -> f32 {
+        let node = &self.nodes[index];
+        let visits = node.visits as f32;
+        node.reward / visits + self.config.exploration * ((node.availability as f32).ln() / visits).sqrt()
+    }
+
+    /// Plays until the end of the game, or scores the players with their share of victory points
+    fn rollout<R : Rng>(&self, state: &mut State, phase: &mut Phase, rng: &mut R) -> Vec<f32> {
+        let player_count = state.player_count() as usize;
+        let mut greedy = Greedy::new();
+        for _ in 0..self.config.max_rollout_actions {
+            if let Phase::FinishedGame { winner } = *phase {
+                let mut rewards = vec![0.0; player_count];
+                rewards[winner.to_usize()] = 1.0;
+                return rewards;
+            }
+            let legal = legal::legal_actions(phase, state);
+            let action = match self.config.rollout {
+                RolloutPolicy::Random => match legal.choose(rng) {
+                    Some(action) => *action,
+                    None => break,
+                },
+                RolloutPolicy::Greedy => greedy.pick_action(phase, &PlayerView::new(state, phase.player()), &legal),
+            };
+            apply(phase, state, action, rng);
+        }
+        if let Phase::FinishedGame { winner } = *phase {
+            let mut rewards = vec![0.0; player_count];
+            rewards[winner.to_usize()] = 1.0;
+            return rewards;
+        }
+        let vps: Vec<f32> = (0..player_count).map(|p| state.get_player_total_vp(PlayerId::from(p)) as f32).collect();
+        let total: f32 = vps.iter().sum();
+        vps.iter().map(|vp| if total > 0.0 { vp / total } else { 1.0 / player_count as f32 }).collect()
+    }
+
+    /// Visits and total reward of each action at the root
+    fn root_visits(&self) -> Vec<(Action, u32, f32)> {
+        self.nodes[0].children.iter()
+            .map(|child| {
+                let node = &self.nodes[*child];
+                (node.action.unwrap(), node.visits, node.reward)
+            })
+            .collect()
+    }
+}
+
+/// Information set Monte Carlo tree search player
+///
+/// Searches with [legal] and [apply] on states sampled from what its seat knows,
+/// using a [HandTracker] to only sample hands that are coherent with the game so far
+pub struct Mcts {
+    config: MctsConfig,
+    rng: SmallRng,
+    tracker: Option<HandTracker>,
+}
+
+impl PickerPlayerTrait for Mcts {
+    type ACTIONS = Vec<Action>;
+    type PICKED = Action;
+
+    fn new_game(&mut self, _: PlayerId, phase: &Phase, view: &PlayerView, _: &Vec<Action>) {
+        self.tracker = Some(HandTracker::new(view, phase));
+    }
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView, legal_actions: &Vec<Action>) -> Action {
+        if legal_actions.len() == 1 {
+            return legal_actions[0];
+        }
+        let tracker = self.tracker.as_ref().expect("Game should have started");
+        let info = InformationSet::new(view, tracker, phase);
+        let statistics = self.search(&info);
+        // Most visited action, the best rewarded one when tied
+        let total = |action: &Action| statistics.iter()
+            .filter(|(other, _, _)| other == action)
+            .fold((0, 0.0), |(visits, reward), (_, v, r)| (visits + v, reward + r));
+        *legal_actions.iter()
+            .max_by(|a, b| total(a).partial_cmp(&total(b)).unwrap())
+            .expect("There is always a legal action")
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        println!("{:?}", error);
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        if let Some(tracker) = self.tracker.as_mut() {
+            tracker.notify(notification);
+        }
+    }
+
+    fn results(&mut self, _: &PlayerView, _: PlayerId) {}
+}
+
+impl Mcts {
+    pub fn new(config: MctsConfig) -> Mcts {
+        Mcts {
+            config,
+            rng: match config.seed {
+                Some(seed) => SmallRng::seed_from_u64(seed),
+                None => SmallRng::from_entropy(),
+            },
+            tracker: None,
+        }
+    }
+
+    pub fn new_player(config: MctsConfig) -> impl CatanPlayer {
+        ActionPickerPlayer::new(Mcts::new(config))
+    }
+
+    /// Searches one tree per thread, and returns the visits and rewards of every action at their roots
+    fn search(&mut self, info: &InformationSet) -> Vec<(Action, u32, f32)> {
+        let config = self.config;
+        let threads = config.threads.max(1);
+        let deadline = config.time_limit.map(|limit| Instant::now() + limit);
+        let seeds: Vec<u64> = (0..threads).map(|_| self.rng.gen()).collect();
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = seeds.into_iter().enumerate()
+                .map(|(i, seed)| {
+                    // Iterations that don't divide evenly go to the first threads
+                    let iterations = config.iterations / threads as u32 + if (i as u32) < config.iterations % threads as u32 { 1 } else { 0 };
+                    scope.spawn(move || {
+                        let mut rng = SmallRng::seed_from_u64(seed);
+                        let mut search = Search::new(config, info);
+                        for _ in 0..iterations {
+                            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                                break;
+                            }
+                            search.iterate(&mut rng);
+                        }
+                        search.root_visits()
+                    })
+                })
+                .collect();
+            handles.into_iter()
+                .flat_map(|handle| handle.join().expect("Search thread panicked"))
+                .collect()
+        })
+    }
+}
@@ -1,29 +1,48 @@
 mod picker_player;
+mod action_space;
 mod randomy;
+mod greedy;
+mod mcts;
+mod tracker;
+mod remote;
 pub mod relative;
+pub mod evaluation;
 
 use crate::utils::Empty;
 pub use picker_player::{ActionPickerPlayer, IndexPickerPlayer, PickerPlayerTrait, generate_possible_actions};
+pub use action_space::{ActionSpace, ActionSection, ACTION_SPACE_VERSION};
 pub use randomy::Randomy;
+pub use greedy::Greedy;
+pub use mcts::{Mcts, MctsConfig, RolloutPolicy, InformationSet};
+pub use tracker::HandTracker;
+pub use remote::{RemotePlayer, RemoteConfig};
 
-use crate::state::{State, PlayerId};
+use crate::state::{PlayerView, PlayerId};
 use crate::game::{Action, Notification, Error, Phase};
 
 pub trait CatanPlayer {
-    fn new_game(&mut self, position: PlayerId, state: &State);
-    fn pick_action(&mut self, phase: &Phase, state: &State) -> Action;
+    /// Starts a game from the given phase, which is [Phase::START_GAME] unless resuming a position
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView);
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action;
     fn bad_action(&mut self, error: Error);
     fn notify(&mut self, notification: &Notification);
-    fn results(&mut self, state: &State, winner: PlayerId);
+    fn results(&mut self, view: &PlayerView, winner: PlayerId);
+
+    /// Does the player read the full state
+    ///
+    /// Players only know what their seat can see, unless they opt in to receive an oracle [PlayerView]
+    fn oracle(&self) -> bool {
+        false
+    }
 }
 
-impl<P : Player<Observation = State, Picked = Action, ExtraNew = Empty, ExtraPick = Empty>>
+impl<P : Player<Picked = Action, ExtraNew = Empty, ExtraPick = Empty>>
     CatanPlayer for P {
-    fn new_game(&mut self, position: PlayerId, state: &State) {
-        P::new_game(self, position, state,  Empty{})
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView) {
+        P::new_game(self, position, phase, view,  Empty{})
     }
-    fn pick_action(&mut self, phase: &Phase, state: &State) -> Action {
-        P::pick_action(self, phase, state, Empty{})
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
+        P::pick_action(self, phase, view, Empty{})
     }
     fn bad_action(&mut self, error: Error) {
         P::bad_action(self, error)
@@ -31,20 +50,19 @@ impl<P : Player<Observation = State, Picked = Action, ExtraNew = Empty, ExtraPic
     fn notify(&mut self, notification: &Notification) {
         P::notify(self, notification)
     }
-    fn results(&mut self, state: &State, winner: PlayerId) {
-        P::results(self, state, winner)
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        P::results(self, view, winner)
     }
 }
 
 pub trait Player{
-    type Observation;
     type Picked;
     type ExtraNew;
     type ExtraPick;
 
-    fn new_game<'f>(&mut self, position: PlayerId, observation: &Self::Observation, extra_new: Self::ExtraNew);
-    fn pick_action<'f>(&mut self, phase: &Phase, observation: &Self::Observation, extra_pick: Self::ExtraPick) -> Self::Picked;
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView, extra_new: Self::ExtraNew);
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView, extra_pick: Self::ExtraPick) -> Self::Picked;
     fn bad_action(&mut self, error: Error);
     fn notify(&mut self, notification: &Notification);
-    fn results(&mut self, state: &State, winner: PlayerId);
+    fn results(&mut self, view: &PlayerView, winner: PlayerId);
 }
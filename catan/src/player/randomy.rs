@@ -2,7 +2,7 @@ use rand::{Rng, SeedableRng};
 use rand::rngs::SmallRng;
 
 use crate::game::{Phase, Action, Error, Notification};
-use crate::state::{State, PlayerId};
+use crate::state::{PlayerView, PlayerId};
 use super::{CatanPlayer, ActionPickerPlayer, PickerPlayerTrait};
 
 pub struct Randomy {
@@ -13,9 +13,9 @@ impl PickerPlayerTrait for Randomy {
     type ACTIONS = Vec<Action>;
     type PICKED = Action;
 
-    fn new_game(&mut self, _: PlayerId, _: &State, _: &Vec<Action>) {}
+    fn new_game(&mut self, _: PlayerId, _: &Phase, _: &PlayerView, _: &Vec<Action>) {}
 
-    fn pick_action(&mut self, _: &Phase, _: &State, legal_actions: &Vec<Action>) -> Action {
+    fn pick_action(&mut self, _: &Phase, _: &PlayerView, legal_actions: &Vec<Action>) -> Action {
         legal_actions[self.rng.gen_range(0, legal_actions.len())]
     }
 
@@ -25,7 +25,7 @@ impl PickerPlayerTrait for Randomy {
 
     fn notify(&mut self, _: &Notification) {}
 
-    fn results(&mut self, _: &State, _:PlayerId) {}
+    fn results(&mut self, _: &PlayerView, _:PlayerId) {}
 }
 
 impl Randomy {
@@ -0,0 +1,401 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::mem;
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, sync_channel, Receiver, RecvTimeoutError, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+
+use crate::game::{Phase, TurnPhase, DevelopmentPhase, Action, Error, Notification};
+use crate::state::{PlayerView, PlayerId};
+use crate::utils::{Hex, LandHex, Harbor, Resource, Resources, DevelopmentCard, DevelopmentCards};
+use super::{CatanPlayer, ActionSpace, Randomy, ACTION_SPACE_VERSION};
+
+/// Messages sent to a bot that it can leave unread before being considered disconnected
+const MAX_PENDING_MESSAGES: usize = 256;
+
+/// Settings of a [RemotePlayer]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RemoteConfig {
+    /// Time the bot has to answer a `pick_action`
+    pub move_timeout: Duration,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        RemoteConfig {
+            move_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Player controlled by an external bot, speaking JSON lines over stdin/stdout or TCP
+///
+/// Every message is one JSON object per line, with a `type` field.
+/// The referee sends:
+/// - `{"type": "new_game", "version", "position", "players", "actions", "phase", "view"}`, where `actions`
+///   lists the names of the seat's [ActionSpace], whose indices are used by the other messages
+/// - `{"type": "pick_action", "id", "phase", "legal", "view"}`, `legal` being action indices
+/// - `{"type": "bad_action", "error"}`
+/// - `{"type": "notify", "notification"}`, actions in notifications being given by name
+/// - `{"type": "results", "winner", "view"}`
+///
+/// The bot only answers `pick_action`, with `{"id", "action"}` where `action` is an index or a name.
+/// Missing the [move_timeout](RemoteConfig::move_timeout), answering an illegal action or
+/// disconnecting makes the fallback player, random by default, play the move instead.
+/// A bot that stops reading its messages is disconnected too.
+/// Once disconnected, the fallback plays until the end
+pub struct RemotePlayer {
+    config: RemoteConfig,
+    writer: SyncSender<String>,
+    flushed: Receiver<()>,
+    lines: Receiver<String>,
+    child: Option<Child>,
+    stream: Option<TcpStream>,
+    connected: bool,
+    fallback: Box<dyn CatanPlayer>,
+    space: Option<ActionSpace>,
+    next_id: u64,
+}
+
+impl RemotePlayer {
+    /// Talks to a bot from its standard input and output
+    pub fn new<R : Read + Send + 'static, W : Write + Send + 'static>(reader: R, mut output: W, config: RemoteConfig) -> RemotePlayer {
+        let (writer, messages) = sync_channel::<String>(MAX_PENDING_MESSAGES);
+        let (done, flushed) = channel();
+        // Lines are written on their own thread so that a bot not reading them can't block the game
+        thread::spawn(move || {
+            for message in messages {
+                if writeln!(output, "{}", message).and_then(|_| output.flush()).is_err() {
+                    break;
+                }
+            }
+            let _ = done.send(());
+        });
+        let (sender, lines) = channel();
+        // Lines are read on their own thread so that waiting for them can time out
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                match line {
+                    Ok(line) => if sender.send(line).is_err() {
+                        break;
+                    },
+                    Err(_) => break,
+                }
+            }
+        });
+        RemotePlayer {
+            config,
+            writer,
+            flushed,
+            lines,
+            child: None,
+            stream: None,
+            connected: true,
+            fallback: Box::new(Randomy::new_player()),
+            space: None,
+            next_id: 0,
+        }
+    }
+
+    /// Spawns a bot as a child process
+    pub fn spawn(program: &str, args: &[&str], config: RemoteConfig) -> io::Result<RemotePlayer> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("Child stdin is piped");
+        let stdout = child.stdout.take().expect("Child stdout is piped");
+        let mut player = RemotePlayer::new(stdout, stdin, config);
+        player.child = Some(child);
+        Ok(player)
+    }
+
+    /// Connects to a bot listening on a TCP socket
+    pub fn connect<A : ToSocketAddrs>(address: A, config: RemoteConfig) -> io::Result<RemotePlayer> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let mut player = RemotePlayer::new(stream.try_clone()?, stream.try_clone()?, config);
+        player.stream = Some(stream);
+        Ok(player)
+    }
+
+    /// Player taking over when the bot fails to answer
+    pub fn with_fallback(mut self, fallback: Box<dyn CatanPlayer>) -> RemotePlayer {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Is the bot still reachable
+    pub fn connected(&self) -> bool {
+        self.connected
+    }
+
+    fn send(&mut self, message: Value) {
+        if !self.connected {
+            return;
+        }
+        // Fails once the writing thread stopped, or when too many messages are pending
+        if self.writer.try_send(message.to_string()).is_err() {
+            self.connected = false;
+        }
+    }
+
+    /// Reply to the `pick_action` with this id, `None` if it didn't come in time
+    fn receive(&mut self, id: u64) -> Option<Value> {
+        let deadline = Instant::now() + self.config.move_timeout;
+        while self.connected {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(left) {
+                Ok(line) => {
+                    let reply: Value = match serde_json::from_str(&line) {
+                        Ok(reply) => reply,
+                        Err(_) => continue,
+                    };
+                    // Late answers to previous moves are skipped
+                    match reply.get("id").and_then(Value::as_u64) {
+                        Some(reply_id) if reply_id != id => continue,
+                        _ => return Some(reply),
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => return None,
+                Err(RecvTimeoutError::Disconnected) => self.connected = false,
+            }
+        }
+        None
+    }
+
+    fn action_name(&self, action: Action) -> Value {
+        self.space.as_ref()
+            .and_then(|space| space.index(action).and_then(|index| space.name(index)))
+            .map(Value::from)
+            .unwrap_or_else(|| Value::from(format!("{:?}", action)))
+    }
+
+    fn notification(&self, notification: &Notification) -> Value {
+        match notification {
+            Notification::ActionPlayed { by, action } => json!({"kind": "action_played", "by": player(*by), "action": self.action_name(*action)}),
+            Notification::ResourcesRolled { roll, resources: rolled } => json!({
+                "kind": "resources_rolled",
+                "roll": roll,
+                "resources": rolled.iter().map(|r| resources(*r)).collect::<Vec<_>>(),
+            }),
+            Notification::InitialResources { player: p, resources: r } => json!({"kind": "initial_resources", "player": player(*p), "resources": resources(*r)}),
+            Notification::Stolen { thief, victim, resource } => json!({
+                "kind": "stolen",
+                "thief": player(*thief),
+                "victim": player(*victim),
+                "resource": resource.map(|resource| resource.to_string()),
+            }),
+            Notification::Discarded { player: p, resources: r } => json!({"kind": "discarded", "player": player(*p), "resources": resources(*r)}),
+            Notification::Monopoly { player: p, resource, taken } => json!({"kind": "monopoly", "player": player(*p), "resource": resource.to_string(), "taken": taken}),
+            Notification::GameFinished { winner } => json!({"kind": "game_finished", "winner": player(*winner)}),
+            Notification::ThiefRolled => json!({"kind": "thief_rolled"}),
+            Notification::InitialPlacementFinished => json!({"kind": "initial_placement_finished"}),
+        }
+    }
+
+    /// Action picked by the bot, if it is legal
+    fn remote_action(&mut self, phase: &Phase, view: &PlayerView, legal_actions: &[Action]) -> Option<Action> {
+        let space = self.space.as_ref()?;
+        let legal: Vec<usize> = legal_actions.iter().filter_map(|action| space.index(*action)).collect();
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({"type": "pick_action", "id": id, "phase": phase_json(phase), "legal": legal, "view": view_json(view)});
+        self.send(message);
+        let reply = self.receive(id)?;
+        let space = self.space.as_ref()?;
+        let action = match reply.get("action") {
+            Some(Value::Number(index)) => index.as_u64().and_then(|index| space.action(index as usize)),
+            Some(Value::String(name)) => space.index_of_name(name).and_then(|index| space.action(index)),
+            _ => None,
+        };
+        match action {
+            Some(action) if legal_actions.contains(&action) => Some(action),
+            _ => {
+                self.send(json!({"type": "bad_action", "error": format!("Unplayable answer {}", reply)}));
+                None
+            }
+        }
+    }
+}
+
+impl CatanPlayer for RemotePlayer {
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView) {
+        let space = ActionSpace::new(view.get_layout(), view.player_count(), position);
+        let actions: Vec<String> = (0..space.len()).filter_map(|index| space.name(index)).collect();
+        self.space = Some(space);
+        self.send(json!({
+            "type": "new_game",
+            "version": ACTION_SPACE_VERSION,
+            "position": position.to_u8(),
+            "players": view.player_count(),
+            "actions": actions,
+            "phase": phase_json(phase),
+            "view": view_json(view),
+        }));
+        self.fallback.new_game(position, phase, view);
+    }
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
+        let legal_actions = view.legal_actions(phase);
+        if legal_actions.len() > 1 {
+            if let Some(action) = self.remote_action(phase, view, &legal_actions) {
+                return action;
+            }
+        } else if let Some(action) = legal_actions.first() {
+            // Nothing to decide
+            return *action;
+        }
+        self.fallback.pick_action(phase, view)
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        self.send(json!({"type": "bad_action", "error": format!("{:?}", error)}));
+        self.fallback.bad_action(error);
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        let message = json!({"type": "notify", "notification": self.notification(notification)});
+        self.send(message);
+        self.fallback.notify(notification);
+    }
+
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        self.send(json!({"type": "results", "winner": player(winner), "view": view_json(view)}));
+        self.fallback.results(view, winner);
+    }
+}
+
+impl Drop for RemotePlayer {
+    fn drop(&mut self) {
+        // Closes the queue, and gives the writing thread some time to send the last messages
+        drop(mem::replace(&mut self.writer, sync_channel(0).0));
+        if self.connected {
+            let _ = self.flushed.recv_timeout(self.config.move_timeout);
+        }
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        // Also stops the reading and writing threads, which hold their own handles on the socket
+        if let Some(stream) = self.stream.as_ref() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn player(player: PlayerId) -> Value {
+    if player == PlayerId::NONE { Value::Null } else { Value::from(player.to_u8()) }
+}
+
+/// Count of each resource, in the order of [Resource::ALL]
+fn resources(resources: Resources) -> Vec<i8> {
+    Resource::ALL.iter().map(|resource| resources[*resource]).collect()
+}
+
+/// Count of each card, in the order of [DevelopmentCard::ALL]
+fn development_cards(cards: DevelopmentCards) -> Vec<u8> {
+    DevelopmentCard::ALL.iter().map(|card| cards[*card]).collect()
+}
+
+fn harbor_name(harbor: Harbor) -> Option<String> {
+    match harbor {
+        Harbor::None => None,
+        Harbor::Generic => Some(String::from("X")),
+        Harbor::Special(resource) => Some(resource.to_string()),
+    }
+}
+
+fn phase_json(phase: &Phase) -> Value {
+    match phase {
+        Phase::InitialPlacement { player: p, placing_second, placing_road } => json!({
+            "kind": "initial_placement",
+            "player": player(*p),
+            "placing_second": placing_second,
+            "placing_road": placing_road,
+        }),
+        Phase::Turn { player: p, turn_phase, development_phase } => json!({
+            "kind": "turn",
+            "player": player(*p),
+            "turn_phase": match turn_phase {
+                TurnPhase::PreRoll => "pre_roll",
+                TurnPhase::Free => "free",
+                TurnPhase::MoveThief => "move_thief",
+                TurnPhase::Discard(_) => "discard",
+            },
+            "discarding": match turn_phase {
+                TurnPhase::Discard(discarding) => player(*discarding),
+                _ => Value::Null,
+            },
+            "development_phase": match development_phase {
+                DevelopmentPhase::Ready => "ready",
+                DevelopmentPhase::KnightActive => "knight_active",
+                DevelopmentPhase::RoadBuildingActive { .. } => "road_building_active",
+                DevelopmentPhase::YearOfPlentyActive { .. } => "year_of_plenty_active",
+                DevelopmentPhase::DevelopmentPlayed => "development_played",
+            },
+        }),
+        Phase::FinishedGame { winner } => json!({"kind": "finished_game", "winner": player(*winner)}),
+    }
+}
+
+/// What the seat can see, hidden cards of other players excluded
+fn view_json(view: &PlayerView) -> Value {
+    let layout = view.get_layout();
+    let hexes: Vec<Value> = layout.hexes.iter().map(|hex| json!({
+        "coord": hex,
+        "hex": match view.get_static_hex(*hex).unwrap_or(Hex::Water) {
+            Hex::Water => String::from("."),
+            Hex::Land(LandHex::Desert) => String::from("D"),
+            Hex::Land(LandHex::Prod(resource, token)) => format!("{}{}", resource, token),
+        },
+    })).collect();
+    let harbors: Vec<Value> = layout.intersections.iter()
+        .filter_map(|intersection| harbor_name(view.get_static_harbor(*intersection).unwrap_or(Harbor::None))
+            .map(|harbor| json!({"coord": intersection, "harbor": harbor})))
+        .collect();
+    let roads: Vec<Value> = layout.paths.iter()
+        .filter_map(|path| view.get_dynamic_path(*path).unwrap_or(None).map(|owner| json!({"coord": path, "player": player(owner)})))
+        .collect();
+    let buildings: Vec<Value> = layout.intersections.iter()
+        .filter_map(|intersection| view.get_dynamic_intersection(*intersection).unwrap_or(None)
+            .map(|(owner, city)| json!({"coord": intersection, "player": player(owner), "city": city})))
+        .collect();
+    let hand = view.get_hand();
+    let public_hands: Vec<Value> = (0..view.player_count()).map(PlayerId::from).map(|p| {
+        let public = view.get_public_hand(p);
+        json!({
+            "resources": public.resource_count,
+            "development_cards": public.development_card_count,
+            "knights": public.knights,
+            "road_length": public.continous_road,
+            "victory_points": view.get_player_public_vp(p),
+        })
+    }).collect();
+    let title = |title: Option<(PlayerId, u8)>| title.map(|(holder, _)| player(holder)).unwrap_or(Value::Null);
+    json!({
+        "player": view.player().to_u8(),
+        "players": view.player_count(),
+        "hexes": hexes,
+        "harbors": harbors,
+        "roads": roads,
+        "buildings": buildings,
+        "thief": view.get_thief_hex(),
+        "bank": resources(view.get_bank_resources()),
+        "development_deck": view.get_development_deck_size(),
+        "hand": {
+            "resources": resources(hand.resources),
+            "development_cards": development_cards(hand.development_cards),
+            "new_development_cards": development_cards(hand.new_development_cards),
+            "pieces": [hand.road_pieces, hand.settlement_pieces, hand.city_pieces],
+            "trade_rates": Resource::ALL.iter().map(|resource| hand.harbor.rate(*resource)).collect::<Vec<_>>(),
+            "victory_points": view.get_total_vp(),
+        },
+        "public_hands": public_hands,
+        "longest_road": title(view.get_longest_road()),
+        "largest_army": title(view.get_largest_army()),
+    })
+}
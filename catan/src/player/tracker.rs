@@ -0,0 +1,262 @@
+use crate::utils::{Coord, Harbor, Resource, Resources, DevelopmentCard, DevelopmentCards};
+use crate::state::{PlayerView, PlayerId, AccessibleHarbor};
+use crate::game::{Phase, Action, Notification};
+
+/// Card counting from public events
+///
+/// Follows the [notifications](Notification) of a game to keep, for every player,
+/// the set of resource hands they could be holding and how many development cards they didn't play yet.
+/// Hands are tracked independently from each other, so after an unknown steal the thief and the victim
+/// both keep every hand that is coherent with what they could have exchanged
+pub struct HandTracker {
+    harbors: Vec<(Coord, Harbor)>,
+    accessible_harbors: Vec<AccessibleHarbor>,
+    hands: Vec<Vec<Resources>>,
+    development_cards: Vec<u8>,
+    played_development_cards: DevelopmentCards,
+    initial_placement: bool,
+    free_roads: u8,
+}
+
+impl HandTracker {
+    /// Tracker of a game starting from the given phase
+    pub fn new(view: &PlayerView, phase: &Phase) -> HandTracker {
+        let player_count = view.player_count() as usize;
+        let mut harbors = Vec::new();
+        for intersection in view.get_layout().intersections.iter() {
+            match view.get_static_harbor(*intersection) {
+                Ok(Harbor::None) | Err(_) => (),
+                Ok(harbor) => harbors.push((*intersection, harbor)),
+            }
+        }
+        // The game can start from any position, where only the seat's hand is known
+        let mut hands = Vec::new();
+        let mut development_cards = Vec::new();
+        let mut accessible_harbors = Vec::new();
+        let mut played_development_cards = DevelopmentCards::new();
+        for p in 0..player_count {
+            let hand = view.get_public_hand(PlayerId::from(p));
+            if PlayerId::from(p) == view.player() {
+                hands.push(vec![view.get_hand().resources]);
+            } else {
+                hands.push(all_hands(hand.resource_count));
+            }
+            development_cards.push(hand.development_card_count);
+            accessible_harbors.push(hand.harbor);
+            played_development_cards.knight += hand.knights;
+        }
+        HandTracker {
+            harbors,
+            accessible_harbors,
+            hands,
+            development_cards,
+            played_development_cards,
+            initial_placement: matches!(phase, Phase::InitialPlacement { .. }),
+            free_roads: 0,
+        }
+    }
+
+    /// Hands the player could be holding
+    pub fn possible_hands(&self, player: PlayerId) -> &[Resources] {
+        &self.hands[player.to_usize()]
+    }
+
+    /// Number of resource cards held by the player
+    pub fn resource_count(&self, player: PlayerId) -> i8 {
+        self.hands[player.to_usize()][0].total()
+    }
+
+    /// Resources the player is sure to hold
+    pub fn certain_resources(&self, player: PlayerId) -> Resources {
+        let hands = &self.hands[player.to_usize()];
+        let mut certain = hands[0];
+        for hand in hands.iter() {
+            for resource in Resource::ALL.iter() {
+                certain[*resource] = certain[*resource].min(hand[*resource]);
+            }
+        }
+        certain
+    }
+
+    /// Average amount of each resource, considering every possible hand equally likely
+    pub fn expected_resources(&self, player: PlayerId) -> [f32; Resource::COUNT] {
+        let hands = &self.hands[player.to_usize()];
+        let mut expected = [0.0; Resource::COUNT];
+        for hand in hands.iter() {
+            for resource in Resource::ALL.iter() {
+                expected[resource.to_usize()] += hand[*resource] as f32;
+            }
+        }
+        for value in expected.iter_mut() {
+            *value /= hands.len() as f32;
+        }
+        expected
+    }
+
+    /// Number of development cards held by the player, bought but not played yet
+    pub fn development_card_count(&self, player: PlayerId) -> u8 {
+        self.development_cards[player.to_usize()]
+    }
+
+    /// Development cards that were played by anyone
+    pub fn played_development_cards(&self) -> DevelopmentCards {
+        self.played_development_cards
+    }
+
+    /// Development cards that could still be in the deck or in an opponent's hand
+    ///
+    /// Removes the played cards and the ones in the given hand from the starting deck
+    pub fn unseen_development_cards(&self, own_cards: DevelopmentCards) -> DevelopmentCards {
+        let mut unseen = DevelopmentCards::STARTING_DECK;
+        for card in DevelopmentCard::ALL.iter() {
+            unseen[*card] = unseen[*card].saturating_sub(self.played_development_cards[*card] + own_cards[*card]);
+        }
+        unseen
+    }
+
+    pub fn notify(&mut self, notification: &Notification) {
+        match notification {
+            Notification::ActionPlayed { by, action } => self.action_played(*by, *action),
+            Notification::ResourcesRolled { roll: _, resources } => {
+                for (player, received) in resources.iter().enumerate() {
+                    self.gain(PlayerId::from(player), *received);
+                }
+            }
+            Notification::InitialResources { player, resources } => self.gain(*player, *resources),
+            Notification::Discarded { player, resources } => self.spend(*player, *resources),
+            Notification::Stolen { thief, victim, resource: Some(resource) } => {
+                let stolen = Resources::new_one(*resource, 1);
+                self.spend(*victim, stolen);
+                self.gain(*thief, stolen);
+            }
+            Notification::Stolen { thief, victim, resource: None } => self.unknown_steal(*thief, *victim),
+            Notification::Monopoly { player, resource, taken } => {
+                for (p, amount) in taken.iter().enumerate() {
+                    if p != player.to_usize() {
+                        self.spend(PlayerId::from(p), Resources::new_one(*resource, *amount));
+                    }
+                }
+                self.gain(*player, Resources::new_one(*resource, taken.iter().sum()));
+            }
+            _ => (),
+        }
+    }
+
+    fn action_played(&mut self, player: PlayerId, action: Action) {
+        match action {
+            Action::RollDice => self.initial_placement = false,
+            Action::EndTurn => self.free_roads = 0,
+            Action::BuildRoad { path: _ } => {
+                if self.free_roads > 0 {
+                    self.free_roads -= 1;
+                } else if !self.initial_placement {
+                    self.spend(player, Resources::ROAD);
+                }
+            }
+            Action::BuildSettlement { intersection } => {
+                if !self.initial_placement {
+                    self.spend(player, Resources::SETTLEMENT);
+                }
+                for (coord, harbor) in self.harbors.iter() {
+                    if *coord == intersection {
+                        self.accessible_harbors[player.to_usize()].add(*harbor);
+                    }
+                }
+            }
+            Action::BuildCity { intersection: _ } => self.spend(player, Resources::CITY),
+            Action::TradeBank { given, asked } => {
+                let rate = self.accessible_harbors[player.to_usize()].rate(given) as i8;
+                self.spend(player, Resources::new_one(given, rate));
+                self.gain(player, Resources::new_one(asked, 1));
+            }
+            Action::BuyDevelopment => {
+                self.spend(player, Resources::DVP_CARD);
+                self.development_cards[player.to_usize()] += 1;
+            }
+            Action::DevelopmentKnight => self.play(player, DevelopmentCard::Knight),
+            Action::DevelopmentRoadBuilding => {
+                self.play(player, DevelopmentCard::RoadBuilding);
+                self.free_roads = 2;
+            }
+            Action::DevelopmentYearOfPlenty => self.play(player, DevelopmentCard::YearOfPlenty),
+            Action::DevelopmentMonopole { resource: _ } => self.play(player, DevelopmentCard::Monopole),
+            Action::ChooseFreeResource { resource } => self.gain(player, Resources::new_one(resource, 1)),
+            _ => (),
+        }
+    }
+
+    fn play(&mut self, player: PlayerId, card: DevelopmentCard) {
+        let count = &mut self.development_cards[player.to_usize()];
+        *count = count.saturating_sub(1);
+        self.played_development_cards[card] += 1;
+    }
+
+    fn gain(&mut self, player: PlayerId, resources: Resources) {
+        for hand in self.hands[player.to_usize()].iter_mut() {
+            *hand += resources;
+        }
+    }
+
+    fn spend(&mut self, player: PlayerId, resources: Resources) {
+        let hands = &mut self.hands[player.to_usize()];
+        let count = hands[0].total() - resources.total();
+        hands.retain(|hand| *hand >= resources);
+        if hands.is_empty() {
+            // Something was missed, only the number of cards can be trusted
+            *hands = all_hands(count);
+        } else {
+            for hand in hands.iter_mut() {
+                *hand -= resources;
+            }
+        }
+    }
+
+    fn unknown_steal(&mut self, thief: PlayerId, victim: PlayerId) {
+        let mut victim_hands = Vec::new();
+        let mut stolen = [false; Resource::COUNT];
+        for hand in self.hands[victim.to_usize()].iter() {
+            for resource in Resource::ALL.iter() {
+                if hand[*resource] > 0 {
+                    stolen[resource.to_usize()] = true;
+                    push_unique(&mut victim_hands, *hand - Resources::new_one(*resource, 1));
+                }
+            }
+        }
+        // Nothing could be stolen
+        if victim_hands.is_empty() {
+            return;
+        }
+        let mut thief_hands = Vec::new();
+        for hand in self.hands[thief.to_usize()].iter() {
+            for resource in Resource::ALL.iter() {
+                if stolen[resource.to_usize()] {
+                    push_unique(&mut thief_hands, *hand + Resources::new_one(*resource, 1));
+                }
+            }
+        }
+        self.hands[victim.to_usize()] = victim_hands;
+        self.hands[thief.to_usize()] = thief_hands;
+    }
+}
+
+fn push_unique(hands: &mut Vec<Resources>, hand: Resources) {
+    if !hands.contains(&hand) {
+        hands.push(hand);
+    }
+}
+
+/// Every hand of the given size
+fn all_hands(count: i8) -> Vec<Resources> {
+    let count = count.max(0);
+    let mut hands = Vec::new();
+    for b in 0..=count {
+        for l in 0..=count-b {
+            for o in 0..=count-(b+l) {
+                for g in 0..=count-(b+l+o) {
+                    hands.push(Resources::new(b, l, o, g, count-(b+l+o+g)));
+                }
+            }
+        }
+    }
+    hands
+}
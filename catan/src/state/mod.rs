@@ -1,17 +1,20 @@
 mod display;
 mod player_hand;
 mod tricell_state;
+mod view;
 pub mod topology;
 
-pub use player_hand::PlayerHand;
+pub use player_hand::{PlayerHand, AccessibleHarbor};
 pub use tricell_state::TricellState;
+pub use view::{PlayerView, PublicHand};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct PlayerId(u8);
 
 use std::any::Any;
+use std::sync::Arc;
 
-use crate::utils::{Hex, Harbor, Coord, DevelopmentCards, Resources};
+use crate::utils::{Hex, Harbor, Coord, DevelopmentCards, DevelopmentDeck, Resources};
 use crate::board::{Layout, Error};
 
 impl PlayerId {
@@ -48,7 +51,7 @@ impl From<usize> for PlayerId {
 }
 
 pub trait StateMaker {
-    fn new_empty(layout: &'static Layout, player_count: u8) -> State;
+    fn new_empty(layout: Arc<Layout>, player_count: u8) -> State;
 }
 
 pub type State = Box<dyn StateTrait>;
@@ -57,11 +60,19 @@ pub trait StateTrait {
 
     fn get_layout(&self) -> &Layout;
 
+    /// Layout shared with the state, for what outlives it
+    fn get_shared_layout(&self) -> &Arc<Layout>;
+
     fn player_count(&self) -> u8;
 
-    fn get_development_cards(&self) -> DevelopmentCards;
+    fn get_development_deck(&self) -> &DevelopmentDeck;
+
+    fn get_development_deck_mut(&mut self) -> &mut DevelopmentDeck;
 
-    fn get_development_cards_mut(&mut self) -> &mut DevelopmentCards;
+    /// Number of cards of each type left in the development deck
+    fn get_development_cards(&self) -> DevelopmentCards {
+        self.get_development_deck().cards()
+    }
 
     fn get_bank_resources(&self) -> Resources;
 
@@ -122,6 +133,12 @@ pub trait StateTrait {
 
     fn update_largest_army(&mut self, player: PlayerId);
 
+    /// Gives the longest road title without looking at the roads
+    fn set_longest_road(&mut self, holder: Option<PlayerId>);
+
+    /// Gives the largest army title without looking at the knights
+    fn set_largest_army(&mut self, holder: Option<PlayerId>);
+
     // Static Board
     fn set_static_hex(&mut self, coord: Coord, hex: Hex) -> Result<(), Error>;
 
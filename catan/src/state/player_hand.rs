@@ -46,6 +46,12 @@ impl AccessibleHarbor {
     }
 }
 
+impl Default for AccessibleHarbor {
+    fn default() -> Self {
+        AccessibleHarbor::new()
+    }
+}
+
 impl Index<Harbor> for AccessibleHarbor {
     type Output = bool;
 
@@ -4,18 +4,12 @@ use crate::board::{Coord, CoordType};
 
 impl<T : StateTrait> RawTopology for T {
     fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
-        let results = Coord::TOPOLOGY.neighbours(coord, center_type, neighbour_type)?;
-        Ok(results.iter()
-            .filter_map(|coord| if self.get_layout().contains_coord(*coord) { Some(*coord) } else { None })
-            .collect())
+        self.get_layout().neighbours(coord, center_type, neighbour_type)
     }
 }
 
 impl RawTopology for State {
     fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
-        let results = Coord::TOPOLOGY.neighbours(coord, center_type, neighbour_type)?;
-        Ok(results.iter()
-            .filter_map(|coord| if self.get_layout().contains_coord(*coord) { Some(*coord) } else { None })
-            .collect())
+        self.get_layout().neighbours(coord, center_type, neighbour_type)
     }
 }
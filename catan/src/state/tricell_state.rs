@@ -1,18 +1,20 @@
 use std::any::Any;
+use std::sync::Arc;
 
 use crate::board::map::TricellMap;
 use crate::board::{Layout, Error};
-use crate::utils::{Empty, Hex, Harbor, Coord, DevelopmentCards, Resources};
+use crate::utils::{Empty, Hex, Harbor, Coord, DevelopmentCards, DevelopmentDeck, Resources};
 use crate::board::utils::topology::Topology;
 use super::PlayerHand;
 use super::{State, StateTrait, StateMaker, PlayerId};
 
+#[derive(Clone)]
 pub struct TricellState {
-    layout: &'static Layout,
+    layout: Arc<Layout>,
     static_board: Box<TricellMap<Hex,Empty,Harbor>>,
     dynamic_board: Box<TricellMap<Empty,PlayerId,(PlayerId,bool)>>,
     thief: Coord,
-    development_card: DevelopmentCards,
+    development_deck: DevelopmentDeck,
     longest_road: PlayerId,
     largest_army: PlayerId,
     discards: Vec<(PlayerId,Option<Resources>)>,
@@ -21,13 +23,13 @@ pub struct TricellState {
 }
 
 impl TricellState {
-    pub fn new(layout: &'static Layout, players: usize) -> TricellState {
+    pub fn new(layout: Arc<Layout>, players: usize) -> TricellState {
         TricellState {
-            layout,
             static_board: TricellMap::new(&layout, Hex::Water, Empty::INSTANCE, Harbor::None),
             dynamic_board: TricellMap::new(&layout, Empty::INSTANCE, PlayerId::NONE, (PlayerId::NONE, false)),
+            layout,
             thief: Coord::ZERO,
-            development_card: DevelopmentCards::new(),
+            development_deck: DevelopmentDeck::OnDemand(DevelopmentCards::new()),
             longest_road: PlayerId::NONE,
             largest_army: PlayerId::NONE,
             discards: Vec::new(),
@@ -124,26 +126,30 @@ struct PathChain {
 }
 
 impl StateMaker for TricellState {
-    fn new_empty(layout: &'static Layout, player_count: u8) -> State {
+    fn new_empty(layout: Arc<Layout>, player_count: u8) -> State {
         Box::new(TricellState::new(layout, player_count as usize))
     }
 }
 
 impl StateTrait for TricellState {
     fn get_layout(&self) -> &Layout {
-        self.layout
+        &self.layout
+    }
+
+    fn get_shared_layout(&self) -> &Arc<Layout> {
+        &self.layout
     }
 
     fn player_count(&self) -> u8 {
         self.players.len() as u8
     }
 
-    fn get_development_cards(&self) -> DevelopmentCards {
-        self.development_card
+    fn get_development_deck(&self) -> &DevelopmentDeck {
+        &self.development_deck
     }
 
-    fn get_development_cards_mut(&mut self) -> &mut DevelopmentCards {
-        &mut self.development_card
+    fn get_development_deck_mut(&mut self) -> &mut DevelopmentDeck {
+        &mut self.development_deck
     }
 
     fn get_bank_resources(&self) -> Resources {
@@ -268,6 +274,14 @@ impl StateTrait for TricellState {
         self.largest_army = player;
     }
 
+    fn set_longest_road(&mut self, holder: Option<PlayerId>) {
+        self.longest_road = holder.unwrap_or(PlayerId::NONE);
+    }
+
+    fn set_largest_army(&mut self, holder: Option<PlayerId>) {
+        self.largest_army = holder.unwrap_or(PlayerId::NONE);
+    }
+
     // --- static board --- //
 
     fn set_static_hex(&mut self, coord: Coord, hex: Hex) -> Result<(), Error>{
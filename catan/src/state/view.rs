@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use crate::utils::{Hex, Harbor, Coord, CoordType, Resources};
+use crate::board::{Layout, Error};
+use crate::board::utils::topology::{RawTopology, TopologyResult};
+use crate::game::{Phase, Action, Error as GameError, legal};
+use crate::player::ActionSpace;
+use super::player_hand::AccessibleHarbor;
+use super::{State, PlayerHand, PlayerId};
+
+/// Public part of a player's hand
+///
+/// What every player at the table knows about a hand: how many cards it holds,
+/// but not which ones, and everything that was put in front of the player
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PublicHand {
+    pub resource_count: i8,
+    pub development_card_count: u8,
+    pub road_pieces: u8,
+    pub settlement_pieces: u8,
+    pub city_pieces: u8,
+    pub building_vp: u8,
+    pub knights: u8,
+    pub continous_road: u8,
+    pub harbor: AccessibleHarbor,
+}
+
+impl From<&PlayerHand> for PublicHand {
+    fn from(hand: &PlayerHand) -> PublicHand {
+        PublicHand {
+            resource_count: hand.resources.total(),
+            development_card_count: hand.development_cards.total() + hand.new_development_cards.total(),
+            road_pieces: hand.road_pieces,
+            settlement_pieces: hand.settlement_pieces,
+            city_pieces: hand.city_pieces,
+            building_vp: hand.building_vp,
+            knights: hand.knights,
+            continous_road: hand.continous_road,
+            harbor: hand.harbor,
+        }
+    }
+}
+
+/// What a seat knows about a state
+///
+/// Wraps a state and only gives access to the board, the bank, the size of the development deck,
+/// the hand of the seat and the [public hands](PublicHand) of the other players.
+/// The full state can only be read from an "oracle" view
+#[derive(Copy, Clone)]
+pub struct PlayerView<'a> {
+    state: &'a State,
+    player: PlayerId,
+    oracle: bool,
+}
+
+impl<'a> PlayerView<'a> {
+    pub fn new(state: &'a State, player: PlayerId) -> PlayerView<'a> {
+        PlayerView {
+            state,
+            player,
+            oracle: false,
+        }
+    }
+
+    /// View that also gives access to the full state
+    pub fn oracle(state: &'a State, player: PlayerId) -> PlayerView<'a> {
+        PlayerView {
+            state,
+            player,
+            oracle: true,
+        }
+    }
+
+    /// The seat this view belongs to
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    pub fn is_oracle(&self) -> bool {
+        self.oracle
+    }
+
+    /// The full state, only available to oracle views
+    pub fn state(&self) -> Option<&'a State> {
+        if self.oracle {
+            Some(self.state)
+        } else {
+            None
+        }
+    }
+
+    /// Is the action legal in this context
+    ///
+    /// Same as [legal](crate::game::legal::legal), but only for actions of the seat,
+    /// since errors could otherwise reveal the hand of another player
+    pub fn legal(&self, phase: &Phase, action: Action) -> Result<(), GameError> {
+        if phase.player() != self.player {
+            return Err(GameError::IncoherentAction(action));
+        }
+        legal::legal(phase, self.state, action)
+    }
+
+    /// Every legal action of the seat, see [legal_actions](crate::game::legal::legal_actions)
+    pub fn legal_actions(&self, phase: &Phase) -> Vec<Action> {
+        if phase.player() != self.player {
+            return Vec::new();
+        }
+        legal::legal_actions(phase, self.state)
+    }
+
+    /// Marks the legal actions of the seat, see [legal_actions_mask](crate::game::legal::legal_actions_mask)
+    pub fn legal_actions_mask(&self, phase: &Phase, space: &ActionSpace, mask: &mut [bool]) {
+        if phase.player() != self.player {
+            mask.iter_mut().for_each(|legal| *legal = false);
+            return;
+        }
+        legal::legal_actions_mask(phase, self.state, space, mask)
+    }
+
+    pub fn get_layout(&self) -> &'a Layout {
+        self.state.get_layout()
+    }
+
+    pub fn get_shared_layout(&self) -> &'a Arc<Layout> {
+        self.state.get_shared_layout()
+    }
+
+    pub fn player_count(&self) -> u8 {
+        self.state.player_count()
+    }
+
+    /// Number of cards left in the development deck
+    pub fn get_development_deck_size(&self) -> u8 {
+        self.state.get_development_deck().size()
+    }
+
+    pub fn get_bank_resources(&self) -> Resources {
+        self.state.get_bank_resources()
+    }
+
+    pub fn get_thief_hex(&self) -> Coord {
+        self.state.get_thief_hex()
+    }
+
+    // Player
+
+    /// The full hand of the seat
+    pub fn get_hand(&self) -> &'a PlayerHand {
+        self.state.get_player_hand(self.player)
+    }
+
+    pub fn get_public_hand(&self, player: PlayerId) -> PublicHand {
+        PublicHand::from(self.state.get_player_hand(player))
+    }
+
+    pub fn get_player_public_vp(&self, player: PlayerId) -> u8 {
+        self.state.get_player_public_vp(player)
+    }
+
+    /// Total victory points of the seat, including its hidden cards
+    pub fn get_total_vp(&self) -> u8 {
+        self.state.get_player_total_vp(self.player)
+    }
+
+    pub fn get_longest_road(&self) -> Option<(PlayerId, u8)> {
+        self.state.get_longest_road()
+    }
+
+    pub fn get_largest_army(&self) -> Option<(PlayerId, u8)> {
+        self.state.get_largest_army()
+    }
+
+    // Static Board
+    pub fn get_static_hex(&self, coord: Coord) -> Result<Hex, Error> {
+        self.state.get_static_hex(coord)
+    }
+
+    pub fn get_static_harbor(&self, coord: Coord) -> Result<Harbor, Error> {
+        self.state.get_static_harbor(coord)
+    }
+
+    // Dynamic Board
+    pub fn get_dynamic_path(&self, coord: Coord) -> Result<Option<PlayerId>, Error> {
+        self.state.get_dynamic_path(coord)
+    }
+
+    pub fn get_dynamic_intersection(&self, coord: Coord) -> Result<Option<(PlayerId, bool)>, Error> {
+        self.state.get_dynamic_intersection(coord)
+    }
+}
+
+impl RawTopology for PlayerView<'_> {
+    fn neighbours(&self, coord: Coord, center_type: CoordType, neighbour_type: CoordType) -> TopologyResult {
+        self.state.neighbours(coord, center_type, neighbour_type)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::game::Action;
+use crate::state::PlayerId;
+use crate::board::{layout, Layout};
+use crate::player::{ActionSpace, ACTION_SPACE_VERSION};
+use crate::utils::{Coord, Resources};
+
+#[test]
+fn action_space_lookups() {
+    for player_count in 2..5 {
+        for p in 0..player_count {
+            let player = PlayerId::from(p);
+            let space = ActionSpace::new(&layout::DEFAULT, player_count, player);
+            assert_eq!(space.version(), ACTION_SPACE_VERSION);
+            // Sections follow each other and cover every index
+            let mut end = 0;
+            for section in space.sections() {
+                assert_eq!(section.start, end);
+                end = section.end();
+            }
+            assert_eq!(end, space.len());
+            for index in 0..space.len() {
+                let action = space.action(index).unwrap();
+                assert_eq!(space.index(action), Some(index));
+                assert_eq!(space.index_of_name(&space.name(index).unwrap()), Some(index));
+            }
+            assert_eq!(space.action(space.len()), None);
+            assert_eq!(space.index(Action::Exit), None);
+            // Paths aren't intersections
+            assert_eq!(space.index(Action::BuildSettlement { intersection: Coord::new(1, 0) }), None);
+        }
+    }
+    let space = ActionSpace::new(&layout::DEFAULT, 4, PlayerId::from(2u8));
+    assert_eq!(space.len(), 19 * 4 + 72 + 2 * 54 + 2 + 20 + 14 + 70);
+    assert_eq!(space.section("keep").map(|section| section.len), Some(70));
+    let thief = space.section("move_thief").unwrap();
+    assert_eq!(space.name(thief.start + 1), Some(format!("MoveThief {} +1", layout::DEFAULT.hexes[0])));
+    assert_eq!(space.action(thief.start + 1), Some(Action::MoveThief { hex: layout::DEFAULT.hexes[0], victim: PlayerId::from(3u8) }));
+    let keep = space.index(Action::Keep { resources: Resources::new(2, 0, 1, 0, 1) }).unwrap();
+    assert_eq!(space.name(keep).as_deref(), Some("Keep BBOW"));
+}
+
+/// Size, section starts and FNV-1a digest of the names of the first seat's actions, for 2 to 4 players
+const VERSION_1: [(usize, [usize; 7], u64); 3] = [
+    (324, [0, 38, 110, 218, 220, 240, 254], 0xedea_556a_c843_63d6),
+    (343, [0, 57, 129, 237, 239, 259, 273], 0x7e53_8ecc_70f8_1e54),
+    (362, [0, 76, 148, 256, 258, 278, 292], 0x862a_8363_7a5e_61f5),
+];
+
+#[test]
+fn action_space_is_pinned_by_its_version() {
+    // Any change of the enumeration needs a new version, and new expected values
+    assert_eq!(ACTION_SPACE_VERSION, 1);
+    for (player_count, (len, starts, digest)) in (2..5).zip(VERSION_1.iter()) {
+        let space = ActionSpace::new(&layout::DEFAULT, player_count, PlayerId::FIRST);
+        assert_eq!(space.len(), *len);
+        let section_starts: Vec<usize> = space.sections().iter().map(|section| section.start).collect();
+        assert_eq!(section_starts, starts.to_vec());
+        let names_digest = (0..space.len())
+            .flat_map(|index| space.name(index).unwrap().into_bytes().into_iter().chain(Some(b'\n')))
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+        assert_eq!(names_digest, *digest, "Action space of {} players changed", player_count);
+    }
+}
+
+#[test]
+fn action_space_ignores_layout_order() {
+    let mut hexes = layout::DEFAULT.hexes.clone();
+    hexes.reverse();
+    let reversed = Layout::from_hexes(hexes).unwrap();
+    assert_ne!(reversed.hexes, layout::DEFAULT.hexes);
+    for player_count in 2..5 {
+        let space = ActionSpace::new(&reversed, player_count, PlayerId::FIRST);
+        assert_eq!(space.actions(), ActionSpace::new(&layout::DEFAULT, player_count, PlayerId::FIRST).actions());
+    }
+}
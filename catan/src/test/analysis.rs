@@ -0,0 +1,23 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::state::{TricellState, PlayerView, PlayerId};
+use crate::board::{setup, analysis};
+
+#[test]
+fn analysis_of_default_board() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 4);
+    let report = analysis::analyse(state.as_ref());
+    assert_eq!(report.pips.iter().map(|pips| *pips as u32).sum::<u32>(), 58);
+    assert_eq!(report.tiles.iter().map(|tiles| *tiles as u32).sum::<u32>(), 18);
+    assert_eq!(report.intersections.len(), 54);
+    assert_eq!(report.harbors.len(), 9);
+    assert!(report.harbors.iter().all(|harbor| harbor.intersections.len() == 2));
+    assert_eq!(report.best_openings.len(), analysis::OPENING_COUNT);
+    let best = report.intersections.iter().map(|value| value.pips).max();
+    assert_eq!(report.best_openings.first().map(|value| value.pips), best);
+    // Players see the same static board
+    assert_eq!(analysis::analyse(&PlayerView::new(&state, PlayerId::FIRST)), report);
+    assert!(report.to_json().starts_with("{\"pips\":"));
+}
@@ -0,0 +1,61 @@
+use crate::board::layout;
+use crate::board::utils::{Axial, AxialCoord, HexDirection, HexCorner, CoordType};
+use crate::board::utils::topology::Topology;
+use crate::utils::Coord;
+
+#[test]
+fn axial_round_trip() {
+    for x in -12..=12 {
+        for y in -8..=8 {
+            let coord = Coord::new(x, y);
+            match AxialCoord::from_coord(coord) {
+                Some(axial) => assert_eq!(axial.to_coord(), coord),
+                None => assert_eq!(coord.get_type(), CoordType::Void),
+            }
+        }
+    }
+    assert_eq!(Axial::from_coord(Coord::new(4, 0)), Some(Axial::new(1, 0)));
+    assert_eq!(Axial::from_coord(Coord::new(2, 2)), Some(Axial::new(1, -1)));
+    assert_eq!(Axial::from_coord(Coord::new(1, 1)), None);
+}
+
+#[test]
+fn axial_matches_topology() {
+    for hex in layout::DEFAULT.hexes.iter() {
+        let axial = Axial::from_coord(*hex).unwrap();
+        let hexes: Vec<Coord> = HexDirection::ALL.iter().map(|direction| axial.neighbour(*direction).to_coord()).collect();
+        let paths: Vec<Coord> = HexDirection::ALL.iter().map(|direction| AxialCoord::Edge(axial, *direction).to_coord()).collect();
+        let intersections: Vec<Coord> = HexCorner::ALL.iter().map(|corner| AxialCoord::Corner(axial, *corner).to_coord()).collect();
+        assert_eq!(hexes, Coord::TOPOLOGY.hex_hex_neighbours(*hex).unwrap().to_vec());
+        assert_eq!(paths, Coord::TOPOLOGY.hex_path_neighbours(*hex).unwrap().to_vec());
+        assert_eq!(intersections, Coord::TOPOLOGY.hex_intersection_neighbours(*hex).unwrap().to_vec());
+        for direction in HexDirection::ALL.iter() {
+            // An edge is the same seen from both of its hexes
+            let edge = AxialCoord::Edge(axial, *direction);
+            let other = AxialCoord::Edge(axial.neighbour(*direction), direction.opposite());
+            assert_eq!(edge.canonical(), other.canonical());
+        }
+        assert_eq!(Coord::ZERO.hex_distance(*hex), Some(axial.distance(Axial::ZERO)));
+    }
+}
+
+#[test]
+fn axial_rings_and_lines() {
+    assert_eq!(Axial::ZERO.ring(0), vec![Axial::ZERO]);
+    let mut spiral: Vec<Coord> = Axial::ZERO.spiral(2).into_iter().map(Coord::from).collect();
+    spiral.sort();
+    let mut hexes = layout::DEFAULT.hexes.clone();
+    hexes.sort();
+    assert_eq!(spiral, hexes);
+    for radius in 1..4 {
+        let ring = Axial::new(1, -2).ring(radius);
+        assert_eq!(ring.len(), 6 * radius as usize);
+        assert!(ring.iter().all(|hex| hex.distance(Axial::new(1, -2)) == radius));
+    }
+    let start = Axial::new(-2, 0);
+    let end = Axial::new(2, -1);
+    let line = start.line(end);
+    assert_eq!(line.len(), start.distance(end) as usize + 1);
+    assert_eq!((line[0], line[line.len() - 1]), (start, end));
+    assert!(line.windows(2).all(|step| step[0].distance(step[1]) == 1));
+}
@@ -0,0 +1,21 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{validate, Phase};
+use crate::state::TricellState;
+use crate::board::{setup, layout};
+use crate::board::setup::{BalanceConstraints, BoardMetrics};
+
+#[test]
+fn balanced_boards_respect_constraints() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let constraints = BalanceConstraints::default();
+    for _ in 0..5 {
+        let (state, metrics) = setup::balanced_setup::<TricellState, SmallRng>(&mut rng, &layout::DEFAULT_SPEC, layout::DEFAULT.clone(), 4, &constraints).unwrap();
+        assert!(metrics.satisfies(&constraints));
+        assert_eq!(metrics.adjacent_red_pairs, 0);
+        assert_eq!(BoardMetrics::of(state.as_ref()), metrics);
+        assert_eq!(metrics.pips.iter().map(|pips| *pips as u32).sum::<u32>(), 58);
+        assert_eq!(validate(&state, &Phase::START_GAME), vec![]);
+    }
+}
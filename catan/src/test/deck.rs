@@ -0,0 +1,25 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::utils::{DevelopmentCard, DevelopmentCards, DevelopmentDeck};
+
+#[test]
+fn development_deck_draws() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut deck = DevelopmentDeck::shuffled(DevelopmentCards::STARTING_DECK, &mut rng);
+    assert_eq!(deck.cards(), DevelopmentCards::STARTING_DECK);
+    // An ordered deck is drawn in the order it was shuffled in
+    for _ in 0..DevelopmentCards::STARTING_DECK.total() {
+        let next = deck.peek();
+        assert!(next.is_some());
+        assert_eq!(deck.draw(&mut rng), next);
+    }
+    assert_eq!(deck.draw(&mut rng), None);
+
+    let mut cards = DevelopmentCards::new();
+    cards.monopole = 2;
+    let mut deck = DevelopmentDeck::OnDemand(cards);
+    assert_eq!(deck.peek(), None);
+    assert_eq!(deck.draw(&mut rng), Some(DevelopmentCard::Monopole));
+    assert_eq!(deck.size(), 1);
+}
@@ -0,0 +1,47 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::Scenario;
+use crate::state::{TricellState, PlayerId};
+use crate::board::setup::BEGINNER;
+use crate::player::evaluation::{Evaluation, EvaluationWeights, FEATURES, evaluate, evaluate_relative};
+use crate::utils::{Coord, DevelopmentCards};
+
+#[test]
+fn evaluation_follows_position() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let (state, _) = BEGINNER.starting_position::<TricellState, SmallRng>(&mut rng, 2).unwrap();
+    let (first, second) = (PlayerId::FIRST, PlayerId::from(1u8));
+    let weights = EvaluationWeights::default();
+    let before = Evaluation::new(state.as_ref(), first);
+    assert_eq!(before.victory_points, 2.0);
+    assert_eq!(before.to_vec().len(), FEATURES.len());
+    assert!(before.expansion > 0.0);
+
+    let mut cards = DevelopmentCards::new();
+    cards.knight = 2;
+    let (state, _) = Scenario::from_state(state)
+        .city(first, Coord::new(4, 1))
+        .development_cards(first, cards)
+        .knights(first, 2)
+        .build()
+        .unwrap();
+    let after = Evaluation::new(state.as_ref(), first);
+    assert_eq!(after.victory_points, 4.0);
+    assert_eq!(after.development_cards, 2.0);
+    assert!((after.largest_army - 2.0 / 3.0).abs() < 1e-6);
+    assert!(after.production.iter().sum::<f32>() > before.production.iter().sum::<f32>());
+    assert!(evaluate(state.as_ref(), first, &weights) > evaluate(state.as_ref(), second, &weights));
+    assert!(evaluate_relative(state.as_ref(), first, &weights) > 0.0);
+    assert!(evaluate_relative(state.as_ref(), second, &weights) < 0.0);
+}
+
+#[test]
+fn weights_round_trip() {
+    let weights = EvaluationWeights::default();
+    assert_eq!(EvaluationWeights::from_vec(&weights.to_vec()), Some(weights));
+    assert_eq!(EvaluationWeights::from_vec(&[1.0]), None);
+    let parsed: EvaluationWeights = serde_json::from_str(r#"{"victory_points": 2.0}"#).unwrap();
+    assert_eq!(parsed.victory_points, 2.0);
+    assert_eq!(parsed.expansion, weights.expansion);
+}
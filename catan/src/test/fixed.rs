@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Notification, Phase, to_notation, from_notation};
+use crate::state::{TricellState, PlayerId};
+use crate::board::setup;
+use crate::board::layout::{self, LayoutSpec};
+use crate::player::Randomy;
+use crate::utils::{Coord, Hex, LandHex, Resource, Resources, DevelopmentDeck};
+
+#[test]
+fn beginner_board_is_fixed() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 4);
+    let other = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 4);
+    for hex in state.get_layout().hexes.iter() {
+        assert_eq!(state.get_static_hex(*hex), other.get_static_hex(*hex));
+    }
+    assert_eq!(state.get_static_hex(Coord::new(-4, 4)), Ok(Hex::Land(LandHex::Prod(Resource::Ore, 10))));
+    assert_eq!(state.get_static_hex(Coord::ZERO), Ok(Hex::Land(LandHex::Desert)));
+    // The beginner board lists the default hexes in another order
+    assert!(Arc::ptr_eq(state.get_shared_layout(), &layout::DEFAULT));
+    let (read, _) = from_notation(&to_notation(&state, &Phase::START_GAME).unwrap()).unwrap();
+    for hex in state.get_layout().hexes.iter() {
+        assert_eq!(read.get_static_hex(*hex), state.get_static_hex(*hex));
+    }
+    assert!(matches!(state.get_development_deck(), DevelopmentDeck::Ordered(stack) if stack.len() == 25));
+}
+
+#[test]
+fn beginner_starting_position_is_playable() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let (mut state, phase) = setup::BEGINNER.starting_position::<TricellState, SmallRng>(&mut rng, 4).unwrap();
+    for p in 0..4u8 {
+        let hand = state.get_player_hand(PlayerId::from(p));
+        assert_eq!(hand.building_vp, 2);
+        assert!(hand.resources != Resources::ZERO);
+    }
+    let mut game = Game::new();
+    for _ in 0..4 {
+        game.add_player(Box::new(Randomy::new_player()));
+    }
+    let notification = game.play_from(&mut rng, &mut state, phase, vec![0, 1, 2, 3]);
+    assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+
+    // Three players leave the last player's placements out
+    let (state, _) = setup::BEGINNER.starting_position::<TricellState, SmallRng>(&mut rng, 3).unwrap();
+    assert_eq!(state.get_player_hand(PlayerId::from(2u8)).building_vp, 2);
+}
+
+#[test]
+fn fixed_boards_registry() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    assert!(setup::fixed_board_names().contains(&String::from("beginner")));
+    let mut board = (*setup::fixed_board("beginner").unwrap()).clone();
+    board.layout.tiles.reverse();
+    board.layout.tokens.reverse();
+    board.starting.truncate(2);
+    let board = setup::FixedBoard::from_toml(&board.to_toml()).unwrap();
+    setup::register_fixed_board("reversed", board.clone()).unwrap();
+    assert_eq!(setup::fixed_board("reversed").as_deref(), Some(&board));
+    let state = board.setup::<TricellState, SmallRng>(&mut rng, 3).unwrap();
+    assert_eq!(state.get_static_hex(Coord::new(4, -4)), Ok(Hex::Land(LandHex::Prod(Resource::Ore, 10))));
+
+    let invalid = setup::FixedBoard { layout: LayoutSpec { tokens: Vec::new(), ..board.layout }, starting: Vec::new() };
+    assert!(setup::register_fixed_board("invalid", invalid).is_err());
+    assert!(setup::fixed_board("invalid").is_none());
+}
@@ -0,0 +1,64 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Scenario, Phase, TurnPhase, DevelopmentPhase, Action, Notification};
+use crate::state::{TricellState, PlayerId, PlayerView};
+use crate::board::{setup, layout};
+use crate::board::utils::topology::Topology;
+use crate::player::{CatanPlayer, Greedy, Randomy};
+use crate::utils::{Resources, DevelopmentCards, DevelopmentDeck};
+
+#[test]
+fn greedy_beats_random_players() {
+    let mut rng = SmallRng::seed_from_u64(42);
+    let mut game = Game::new();
+    game.add_player(Box::new(Greedy::new_player()));
+    game.add_player(Box::new(Randomy::new_player()));
+    game.add_player(Box::new(Randomy::new_player()));
+    let games = 6;
+    let mut wins = 0;
+    for i in 0..games {
+        let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, 3);
+        // Greedy takes every seat in turn
+        let order: Vec<usize> = (0..3).map(|seat| (seat + 3 - i % 3) % 3).collect();
+        let greedy_seat = PlayerId::from(i % 3);
+        let notification = game.play(&mut rng, &mut state, order);
+        assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+        if notification == (Notification::GameFinished { winner: greedy_seat }) {
+            wins += 1;
+        }
+    }
+    assert!(wins >= games - 1, "Greedy only won {} games out of {}", wins, games);
+}
+
+#[test]
+fn greedy_prefers_cities_when_builds_are_as_close() {
+    let mut rng = SmallRng::seed_from_u64(3);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 2);
+    let first = layout::DEFAULT.intersections[0];
+    let last = *layout::DEFAULT.intersections.last().unwrap();
+    let first_road = state.intersection_path_neighbours(first).unwrap()[0];
+    let last_road = state.intersection_path_neighbours(last).unwrap()[0];
+    // With an empty deck, a city and a road both miss one card of this hand
+    let phase = Phase::Turn {
+        player: PlayerId::FIRST,
+        turn_phase: TurnPhase::Discard(PlayerId::FIRST),
+        development_phase: DevelopmentPhase::Ready,
+    };
+    let (state, phase) = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, first)
+        .road(PlayerId::FIRST, first_road)
+        .settlement(PlayerId::from(1u8), last)
+        .road(PlayerId::from(1u8), last_road)
+        .resources(PlayerId::FIRST, Resources::new(1, 0, 2, 2, 3))
+        .development_cards(PlayerId::from(1u8), DevelopmentCards::STARTING_DECK)
+        .development_deck(DevelopmentDeck::Ordered(Vec::new()))
+        .discards(vec![(PlayerId::FIRST, None)])
+        .phase(phase)
+        .build()
+        .unwrap();
+    let view = PlayerView::new(&state, PlayerId::FIRST);
+    let mut greedy = Greedy::new_player();
+    greedy.new_game(PlayerId::FIRST, &phase, &view);
+    assert_eq!(greedy.pick_action(&phase, &view), Action::Keep { resources: Resources::new(0, 0, 2, 2, 0) });
+}
@@ -0,0 +1,73 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Notification, Phase, validate};
+use crate::state::{TricellState, PlayerId};
+use crate::board::setup;
+use crate::board::layout::{IslandParams, IslandError, random_island_spec};
+use crate::board::utils::Axial;
+use crate::player::Randomy;
+use crate::utils::Coord;
+
+/// Groups hexes into connected landmasses
+fn landmasses(hexes: &[Coord]) -> usize {
+    let mut seen: Vec<Coord> = Vec::new();
+    let mut count = 0;
+    for hex in hexes.iter() {
+        if seen.contains(hex) {
+            continue;
+        }
+        count += 1;
+        let mut stack = vec![*hex];
+        while let Some(hex) = stack.pop() {
+            if seen.contains(&hex) {
+                continue;
+            }
+            seen.push(hex);
+            let axial = Axial::from_coord(hex).unwrap();
+            stack.extend(hexes.iter().filter(|other| Axial::from_coord(**other).unwrap().distance(axial) == 1));
+        }
+    }
+    count
+}
+
+#[test]
+fn islands_have_requested_shape() {
+    let mut rng = SmallRng::seed_from_u64(5);
+    for (land_hexes, islands, max_radius) in [(19, 1, 3), (12, 1, 4), (24, 3, 5), (30, 2, 4)].iter() {
+        let params = IslandParams { land_hexes: *land_hexes, islands: *islands, max_radius: *max_radius, ..IslandParams::default() };
+        let spec = random_island_spec(&mut rng, &params).unwrap();
+        let hexes = spec.hex_coords();
+        assert_eq!(hexes.len(), *land_hexes);
+        assert_eq!(landmasses(&hexes), *islands);
+        assert!(hexes.iter().all(|hex| Axial::from_coord(*hex).unwrap().distance(Axial::ZERO) <= *max_radius));
+        assert_eq!(spec.harbors.len(), params.harbors);
+        assert!(spec.validate().is_ok());
+    }
+    let impossible = IslandParams { land_hexes: 40, max_radius: 2, max_attempts: 10, ..IslandParams::default() };
+    assert_eq!(random_island_spec(&mut rng, &impossible), Err(IslandError::NoRoom));
+    let invalid = IslandParams { islands: 0, ..IslandParams::default() };
+    assert_eq!(random_island_spec(&mut rng, &invalid), Err(IslandError::InvalidParams));
+    let invalid = IslandParams { max_radius: IslandParams::MAX_RADIUS + 1, ..IslandParams::default() };
+    assert_eq!(random_island_spec(&mut rng, &invalid), Err(IslandError::InvalidParams));
+    // Islands can be seeded on the edge of the largest radius
+    let widest = IslandParams { land_hexes: 20, islands: 10, max_radius: IslandParams::MAX_RADIUS, ..IslandParams::default() };
+    for _ in 0..3 {
+        let spec = random_island_spec(&mut rng, &widest).unwrap();
+        assert!(spec.layout().is_ok());
+    }
+}
+
+#[test]
+fn islands_are_playable() {
+    let mut rng = SmallRng::seed_from_u64(6);
+    let params = IslandParams { land_hexes: 25, islands: 2, max_radius: 4, ..IslandParams::default() };
+    let mut state = setup::random_island_setup::<TricellState, SmallRng>(&mut rng, &params, 3).unwrap();
+    assert_eq!(validate(&state, &Phase::START_GAME), Vec::new());
+    let mut game = Game::new();
+    for _ in 0..3 {
+        game.add_player(Box::new(Randomy::new_player()));
+    }
+    let notification = game.play(&mut rng, &mut state, vec![0, 1, 2]);
+    assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+}
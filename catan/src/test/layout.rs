@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{validate, Phase};
+use crate::state::TricellState;
+use crate::board::{setup, layout};
+use crate::board::layout::{LayoutSpec, SpecError};
+use crate::board::CoordType;
+use crate::board::utils::topology::RawTopology;
+use crate::utils::{Coord, Hex, LandHex, Resource};
+
+const SMALL: &str = r#"
+hexes = [[0, 0], [4, 0], [2, 2]]
+tiles = ["ore", "desert", "wool"]
+tokens = [6, 8]
+track = [[0, 0], [4, 0], [2, 2]]
+harbors = [[-2, 0]]
+harbor_tiles = ["generic"]
+"#;
+
+#[test]
+fn layout_spec_round_trip() {
+    let spec = layout::DEFAULT_SPEC.clone();
+    assert_eq!(spec.validate(), Ok(()));
+    assert_eq!(LayoutSpec::from_toml(&spec.to_toml()), Ok(spec.clone()));
+    assert_eq!(LayoutSpec::from_json(&spec.to_json()), Ok(spec));
+    assert_eq!(layout::DEFAULT.hexes.len(), 19);
+}
+
+#[test]
+fn layout_spec_fills_custom_board() {
+    let spec = LayoutSpec::from_toml(SMALL).unwrap();
+    let layout = Arc::new(spec.layout().unwrap());
+    assert_eq!(layout.hexes.len(), 3);
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_spec_setup::<TricellState, SmallRng>(&mut rng, &spec, layout.clone(), 2).unwrap();
+    assert_eq!(validate(&state, &Phase::START_GAME), vec![]);
+    let ores = layout.hexes.iter()
+        .filter(|hex| state.get_static_hex(**hex) == Ok(Hex::Land(LandHex::Prod(Resource::Ore, 6))) || state.get_static_hex(**hex) == Ok(Hex::Land(LandHex::Prod(Resource::Ore, 8))))
+        .count();
+    assert_eq!(ores, 1);
+
+    let mut broken = spec.clone();
+    broken.tokens.pop();
+    assert_eq!(broken.validate(), Err(SpecError::TokenCount { producing: 2, tokens: 1 }));
+    for token in [0, 1, 7, 13].iter() {
+        let mut broken = spec.clone();
+        broken.tokens[0] = *token;
+        assert_eq!(broken.validate(), Err(SpecError::InvalidToken(*token)));
+    }
+    let mut broken = spec;
+    broken.harbors = vec![[1, 1]];
+    assert_eq!(broken.validate(), Err(SpecError::HarborNotCoastal(Coord::new(1, 1))));
+}
+
+#[test]
+fn precomputed_adjacency_matches_topology() {
+    let layout = &layout::DEFAULT;
+    let types = [CoordType::Hex, CoordType::Path, CoordType::Intersection];
+    let half_width = layout.half_width as i8 + 2;
+    let half_height = layout.half_height as i8 + 2;
+    for x in -half_width..=half_width {
+        for y in -half_height..=half_height {
+            let coord = Coord::new(x, y);
+            for center_type in types.iter() {
+                for neighbour_type in types.iter() {
+                    let expected = Coord::TOPOLOGY.neighbours(coord, *center_type, *neighbour_type)
+                        .map(|neighbours| neighbours.filtered(|neighbour| layout.contains_coord(neighbour)));
+                    assert_eq!(layout.neighbours(coord, *center_type, *neighbour_type), expected);
+                }
+            }
+        }
+    }
+}
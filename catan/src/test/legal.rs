@@ -0,0 +1,41 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+
+use crate::game::{Phase, apply};
+use crate::game::legal::{legal, legal_actions, legal_actions_mask};
+use crate::state::{TricellState, PlayerView};
+use crate::board::setup;
+use crate::player::{ActionSpace, generate_possible_actions};
+
+#[test]
+fn legal_actions_match_brute_force() {
+    for seed in 0..6 {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let player_count = 2 + seed as u8 % 3;
+        let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, player_count);
+        let mut phase = Phase::START_GAME;
+        let mut possible_actions = Vec::new();
+        for _ in 0..3000 {
+            if let Phase::FinishedGame { .. } = phase {
+                break;
+            }
+            generate_possible_actions(&mut possible_actions, &PlayerView::new(&state, phase.player()));
+            let brute_force: Vec<_> = possible_actions.iter().cloned()
+                .filter(|action| legal(&phase, &state, *action).is_ok())
+                .collect();
+            let actions = legal_actions(&phase, &state);
+            assert_eq!(actions, brute_force, "{:?}", phase);
+            let space = ActionSpace::new(state.get_layout(), player_count, phase.player());
+            let mut mask = vec![true; space.len()];
+            legal_actions_mask(&phase, &state, &space, &mut mask);
+            let masked: Vec<_> = space.actions().iter().zip(mask.iter())
+                .filter(|(_, legal)| **legal)
+                .map(|(action, _)| *action)
+                .collect();
+            assert_eq!(masked, brute_force);
+            let action = *actions.choose(&mut rng).unwrap();
+            apply(&mut phase, &mut state, action, &mut rng);
+        }
+    }
+}
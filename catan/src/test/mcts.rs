@@ -0,0 +1,73 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Scenario, Phase, TurnPhase, DevelopmentPhase, Action, validate, legal};
+use crate::state::{State, TricellState, PlayerView, PlayerId};
+use crate::board::setup::BEGINNER;
+use crate::player::{Mcts, MctsConfig, RolloutPolicy, InformationSet, HandTracker, CatanPlayer};
+use crate::utils::{Coord, Resources, DevelopmentCards, DevelopmentDeck};
+
+/// First player is one city away from winning, with four settlements and five victory point cards
+fn winning_position() -> (State, Phase) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let (state, _) = BEGINNER.starting_position::<TricellState, SmallRng>(&mut rng, 2).unwrap();
+    let first = PlayerId::FIRST;
+    let mut deck = DevelopmentCards::STARTING_DECK;
+    deck.victory_point = 0;
+    let mut cards = DevelopmentCards::new();
+    cards.victory_point = 5;
+    Scenario::from_state(state)
+        .settlement(first, Coord::new(4, 1))
+        .settlement(first, Coord::new(-6, -1))
+        .development_cards(first, cards)
+        .development_deck(DevelopmentDeck::OnDemand(deck))
+        .resources(first, Resources::CITY)
+        .phase(Phase::Turn { player: first, turn_phase: TurnPhase::Free, development_phase: DevelopmentPhase::Ready })
+        .build()
+        .unwrap()
+}
+
+fn config() -> MctsConfig {
+    MctsConfig { iterations: 40, max_rollout_actions: 4, seed: Some(3), ..MctsConfig::default() }
+}
+
+#[test]
+fn determinizations_are_coherent() {
+    let (state, phase) = winning_position();
+    let second = PlayerId::from(1u8);
+    let view = PlayerView::new(&state, second);
+    let info = InformationSet::new(&view, &HandTracker::new(&view, &phase), &phase);
+    let mut rng = SmallRng::seed_from_u64(1);
+    for _ in 0..20 {
+        let (sample, sample_phase) = info.sample(&mut rng);
+        assert_eq!(validate(&sample, &sample_phase), Vec::new());
+        assert_eq!(sample.get_player_hand(second), state.get_player_hand(second));
+        let first = sample.get_player_hand(PlayerId::FIRST);
+        assert_eq!(first.resources.total(), Resources::CITY.total());
+        assert_eq!(first.development_cards.total(), 5);
+        assert_eq!(sample.get_development_deck().size(), state.get_development_deck().size());
+    }
+}
+
+#[test]
+fn mcts_finds_winning_city() {
+    let (state, phase) = winning_position();
+    for rollout in [RolloutPolicy::Random, RolloutPolicy::Greedy].iter() {
+        let mut player = Mcts::new_player(MctsConfig { rollout: *rollout, ..config() });
+        let view = PlayerView::new(&state, PlayerId::FIRST);
+        player.new_game(PlayerId::FIRST, &phase, &view);
+        let action = player.pick_action(&phase, &view);
+        assert!(matches!(action, Action::BuildCity { .. }), "{:?} rollouts picked {:?}", rollout, action);
+    }
+}
+
+#[test]
+fn parallel_search_picks_legal_opening() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = crate::board::setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 3);
+    let mut player = Mcts::new_player(MctsConfig { threads: 3, ..config() });
+    let view = PlayerView::new(&state, PlayerId::FIRST);
+    player.new_game(PlayerId::FIRST, &Phase::START_GAME, &view);
+    let action = player.pick_action(&Phase::START_GAME, &view);
+    assert!(legal::legal(&Phase::START_GAME, &state, action).is_ok());
+}
@@ -1,3 +1,25 @@
+mod action_space;
+mod analysis;
+mod axial;
+mod balanced;
+mod deck;
+mod evaluation;
+mod fixed;
+mod greedy;
+mod islands;
+mod layout;
+mod legal;
+mod mcts;
+mod notation;
+mod opening;
+mod random;
+mod remote;
+mod reach;
+mod scenario;
+mod symmetry;
+mod tracker;
+mod validate;
+
 use crate::game::{Game, Notification};
 use crate::state::PlayerId;
 use crate::player::Randomy;
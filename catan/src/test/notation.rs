@@ -0,0 +1,124 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Action, Error, Notification, Phase, to_notation, from_notation, NotationError};
+use crate::state::{PlayerView, PlayerId, TricellState};
+use crate::board::setup;
+use crate::board::layout::IslandParams;
+use crate::player::{CatanPlayer, Randomy};
+
+/// Random player checking that every position it sees can be written and read back
+struct NotatingRandomy {
+    player: Box<dyn CatanPlayer>,
+}
+
+impl CatanPlayer for NotatingRandomy {
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView) {
+        self.player.new_game(position, phase, view)
+    }
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
+        let notation = to_notation(view.state().unwrap(), phase).unwrap();
+        let (state, read_phase) = from_notation(&notation).unwrap();
+        assert_eq!(read_phase, *phase);
+        assert_eq!(to_notation(&state, &read_phase).unwrap(), notation);
+        self.player.pick_action(phase, view)
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        self.player.bad_action(error)
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        self.player.notify(notification)
+    }
+
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        self.player.results(view, winner)
+    }
+
+    fn oracle(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn notation_round_trip() {
+    let mut game = Game::new();
+    for _ in 0..3 {
+        game.add_player(Box::new(NotatingRandomy { player: Box::new(Randomy::new_player()) }));
+    }
+    game.setup_and_play();
+}
+
+#[test]
+fn notation_rejects_bad_input() {
+    assert_eq!(from_notation("").err(), Some(NotationError::MissingField("hexes")));
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 2);
+    let notation = to_notation(&state, &Phase::START_GAME).unwrap();
+    assert!(from_notation(&notation).is_ok());
+    let notation = notation.replace("i0:1:s", "i0:3:s");
+    assert_eq!(from_notation(&notation).err(), Some(NotationError::InvalidField("phase", String::from("i0:3:s"))));
+}
+
+#[test]
+fn notation_rejects_unknown_players() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 2);
+    let notation = to_notation(&state, &Phase::START_GAME).unwrap();
+    let fields: Vec<&str> = notation.split(' ').collect();
+    let invalid = |field: usize, token: &str, name: &'static str| {
+        let mut fields = fields.clone();
+        fields[field] = token;
+        assert_eq!(from_notation(&fields.join(" ")).err(), Some(NotationError::InvalidField(name, String::from(token))));
+    };
+    invalid(8, "7,-", "titles");
+    invalid(8, "-,2", "titles");
+    invalid(9, "i7:1:s", "phase");
+    invalid(9, "t2:p:r", "phase");
+    invalid(9, "t0:d2:r", "phase");
+    invalid(9, "w2", "phase");
+}
+
+#[test]
+fn notation_rejects_out_of_range_values() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 2);
+    let notation = to_notation(&state, &Phase::START_GAME).unwrap();
+    let fields: Vec<&str> = notation.split(' ').collect();
+    let rejected = |field: usize, value: &str, token: &str| {
+        let mut fields: Vec<String> = fields.iter().map(|field| field.to_string()).collect();
+        fields[field] = String::from(value);
+        let name = ["hexes", "harbors", "roads", "buildings", "thief", "hands", "bank", "deck", "titles", "phase", "discards"][field];
+        assert_eq!(from_notation(&fields.join(" ")).err(), Some(NotationError::InvalidField(name, String::from(token))));
+    };
+    // Tokens
+    let hexes: Vec<&str> = fields[0].split(',').collect();
+    let land = hexes.iter().position(|hex| *hex != "." && *hex != "D").unwrap();
+    for token in ["O99", "O7", "O1", "O13"].iter() {
+        let mut changed = hexes.clone();
+        changed[land] = token;
+        rejected(0, &changed.join(","), token);
+    }
+    // Hands
+    let empty = "0,0,0,0,0:0,0,0,0,0:0,0,0,0,0:0:0";
+    for hand in ["100,0,0,0,0:0,0,0,0,0:0,0,0,0,0:0:0", "0,0,0,0,0:15,0,0,0,0:0,0,0,0,0:0:0", "0,0,0,0,0:0,0,0,0,0:0,0,0,0,0:255:0"].iter() {
+        rejected(5, &format!("{}/{}", hand, empty), hand);
+    }
+    let many = vec![empty; 300].join("/");
+    rejected(5, &many, &many);
+    rejected(5, empty, empty);
+    // Bank and deck
+    rejected(6, "127,127,127,127,127", "127,127,127,127,127");
+    rejected(7, "15,0,0,0,0", "15,0,0,0,0");
+    rejected(7, "KKKKKKKKKKKKKKK", "KKKKKKKKKKKKKKK");
+    rejected(10, "0=40,0,0,0,0", "0=40,0,0,0,0");
+}
+
+#[test]
+fn notation_refuses_other_layouts() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::random_island_setup::<TricellState, SmallRng>(&mut rng, &IslandParams::default(), 3).unwrap();
+    assert_eq!(to_notation(&state, &Phase::START_GAME), Err(NotationError::UnsupportedLayout));
+}
@@ -0,0 +1,54 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::Scenario;
+use crate::state::{TricellState, PlayerId, PlayerView};
+use crate::board::setup;
+use crate::board::opening::{rank_openings, opening_road, unconnected_settlement, OpeningWeights};
+use crate::board::reach::settlement_site;
+use crate::board::utils::topology::Topology;
+use crate::utils::Coord;
+
+#[test]
+fn openings_are_legal_and_ranked() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 4);
+    let weights = OpeningWeights::default();
+    let first = rank_openings(state.as_ref(), PlayerId::FIRST, 4, &weights);
+    assert!(!first.is_empty());
+    assert!(first.windows(2).all(|pair| pair[0].value >= pair[1].value));
+    for candidate in first.iter() {
+        assert!(settlement_site(state.as_ref(), candidate.settlement));
+        assert!(state.intersection_path_neighbours(candidate.settlement).unwrap().contains(&candidate.road));
+        let follow_up = candidate.follow_up.unwrap();
+        assert!(!state.intersection_intersection_neighbours(candidate.settlement).unwrap().contains(&follow_up));
+    }
+    // The last seat places twice in a row, so its second settlement can't be taken
+    let last = rank_openings(state.as_ref(), PlayerId::from(3u8), 4, &weights);
+    for candidate in first.iter() {
+        let same = last.iter().find(|other| other.settlement == candidate.settlement && other.road == candidate.road).unwrap();
+        assert!(same.value >= candidate.value);
+    }
+    // Players see the same ranking
+    let view = PlayerView::new(&state, PlayerId::FIRST);
+    assert_eq!(rank_openings(&view, PlayerId::FIRST, 4, &weights), first);
+}
+
+#[test]
+fn second_opening_and_road() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 2);
+    let home = Coord::new(0, 1);
+    let (state, _) = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, home)
+        .build()
+        .unwrap();
+    let weights = OpeningWeights::default();
+    let second = rank_openings(state.as_ref(), PlayerId::FIRST, 2, &weights);
+    assert!(!second.is_empty());
+    assert!(second.iter().all(|candidate| candidate.follow_up.is_none() && candidate.settlement != home));
+    assert_eq!(unconnected_settlement(state.as_ref(), PlayerId::FIRST), Some(home));
+    assert_eq!(unconnected_settlement(state.as_ref(), PlayerId::from(1u8)), None);
+    let road = opening_road(state.as_ref(), PlayerId::FIRST, home, &weights).unwrap();
+    assert!(state.intersection_path_neighbours(home).unwrap().contains(&road));
+}
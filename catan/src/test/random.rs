@@ -0,0 +1,38 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, GameRandom, GameRng, Notification};
+use crate::state::{TricellState, PlayerId};
+use crate::board::setup;
+use crate::player::Greedy;
+use crate::utils::DevelopmentCards;
+
+#[test]
+fn dice_ignore_other_events() {
+    let mut a = GameRng::seed_from_u64(5);
+    let mut b = GameRng::seed_from_u64(5);
+    assert_eq!(a.development_deck(DevelopmentCards::STARTING_DECK), b.development_deck(DevelopmentCards::STARTING_DECK));
+    for i in 0..50 {
+        // Steals and discards only consume the events of one of them
+        for _ in 0..i % 3 {
+            a.events().gen::<u32>();
+        }
+        let roll = a.roll_dice();
+        assert_eq!(roll, b.roll_dice());
+        assert!((2..=12).contains(&roll));
+    }
+    let mut c = GameRng::seed_from_u64(6);
+    assert_ne!((0..10).map(|_| a.roll_dice()).collect::<Vec<_>>(), (0..10).map(|_| c.roll_dice()).collect::<Vec<_>>());
+}
+
+#[test]
+fn play_with_game_rng() {
+    let mut game = Game::new();
+    game.add_player(Box::new(Greedy::new_player()));
+    game.add_player(Box::new(Greedy::new_player()));
+    let mut state = setup::random_default::<TricellState, SmallRng>(&mut SmallRng::seed_from_u64(1), 2);
+    let mut rng = GameRng::seed_from_u64(1);
+    *state.get_development_deck_mut() = rng.development_deck(DevelopmentCards::STARTING_DECK);
+    let notification = game.play(&mut rng, &mut state, vec![0, 1]);
+    assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+}
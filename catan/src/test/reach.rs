@@ -0,0 +1,69 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Scenario, Phase};
+use crate::state::{TricellState, PlayerId, PlayerView};
+use crate::board::setup;
+use crate::board::reach::{RoadDistances, settlement_site};
+use crate::board::utils::topology::Topology;
+use crate::utils::Coord;
+
+#[test]
+fn road_distances_follow_the_network() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let state = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 2);
+    let home = Coord::new(0, 1);
+    let road = Coord::new(1, 1);
+    let (state, _) = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, home)
+        .road(PlayerId::FIRST, road)
+        .phase(Phase::START_TURNS)
+        .build()
+        .unwrap();
+    let distances = RoadDistances::new(state.as_ref(), PlayerId::FIRST);
+    assert_eq!(distances.distance(home), Some(0));
+    assert_eq!(distances.distance(Coord::new(2, 1)), Some(0));
+    for (intersection, distance) in distances.reachable() {
+        let plan = distances.road_plan(intersection).unwrap();
+        assert_eq!(plan.len(), distance as usize);
+        // Each road of the plan touches the previous one or the network
+        let mut network: Vec<Coord> = vec![road];
+        for path in plan {
+            let touching = state.path_path_neighbours(path).unwrap().iter().any(|neighbour| network.contains(neighbour));
+            assert!(touching);
+            network.push(path);
+        }
+    }
+    let sites = distances.settlement_sites(state.as_ref());
+    assert!(sites.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(sites.iter().all(|(site, _)| settlement_site(state.as_ref(), *site)));
+    assert!(distances.cut_off_sites(state.as_ref()).is_empty());
+    let view = PlayerView::new(&state, PlayerId::from(1u8));
+    let opponent = RoadDistances::new(&view, PlayerId::from(1u8));
+    assert!(opponent.reachable().is_empty());
+    assert!(opponent.cut_off_sites(state.as_ref()).is_empty());
+}
+
+#[test]
+fn opponents_block_roads() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    // Along the top coast, the second player's settlement stands between the first player and its target
+    let state = setup::beginner_setup::<TricellState, SmallRng>(&mut rng, 2);
+    let wall = Coord::new(0, 5);
+    let target = Coord::new(2, 5);
+    let (state, _) = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, Coord::new(-4, 5))
+        .road(PlayerId::FIRST, Coord::new(-3, 5))
+        .settlement(PlayerId::from(1u8), wall)
+        .road(PlayerId::from(1u8), Coord::new(1, 5))
+        .phase(Phase::START_TURNS)
+        .build()
+        .unwrap();
+    let distances = RoadDistances::new(state.as_ref(), PlayerId::FIRST);
+    assert_eq!(distances.distance(wall), Some(1));
+    // Going through the wall would take two roads, going around takes four
+    assert_eq!(distances.distance(target), Some(4));
+    assert_eq!(distances.road_plan(target).unwrap().len(), 4);
+    let second = RoadDistances::new(state.as_ref(), PlayerId::from(1u8));
+    assert_eq!(second.distance(target), Some(0));
+}
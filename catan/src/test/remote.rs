@@ -0,0 +1,100 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::channel;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use serde_json::{json, Value};
+
+use crate::game::{Game, Notification};
+use crate::state::PlayerId;
+use crate::player::{CatanPlayer, Randomy, RemotePlayer, RemoteConfig};
+
+/// Listens for one referee and answers with `answer` until the connection closes, returning the received message types
+fn bot<F : Fn(&Value) -> Option<Value> + Send + 'static>(answer: F) -> (u16, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut types = Vec::new();
+        for line in BufReader::new(stream).lines() {
+            let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+            types.push(message["type"].as_str().unwrap().to_string());
+            if message["type"] == "pick_action" {
+                match answer(&message) {
+                    Some(action) => writeln!(writer, "{}", json!({"id": message["id"], "action": action})).unwrap(),
+                    None => break,
+                }
+            }
+        }
+        types
+    });
+    (port, handle)
+}
+
+fn play(remote: RemotePlayer) -> Notification {
+    let mut game = Game::new();
+    game.add_player(Box::new(remote));
+    game.add_player(Box::new(Randomy::new_player()));
+    game.add_player(Box::new(Randomy::new_player()));
+    game.add_player(Box::new(Randomy::new_player()));
+    game.setup_and_play()
+}
+
+#[test]
+fn remote_plays_over_tcp() {
+    let (port, handle) = bot(|message| Some(message["legal"][0].clone()));
+    let remote = RemotePlayer::connect(("127.0.0.1", port), RemoteConfig::default()).unwrap();
+    let notification = play(remote);
+    assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+    let types = handle.join().unwrap();
+    assert_eq!(types.first().map(String::as_str), Some("new_game"));
+    assert_eq!(types.last().map(String::as_str), Some("results"));
+    assert!(types.iter().any(|kind| kind == "pick_action"));
+    assert!(types.iter().any(|kind| kind == "notify"));
+}
+
+#[test]
+fn remote_falls_back_on_failures() {
+    // Illegal answers
+    let (port, handle) = bot(|_| Some(json!("Exit")));
+    let remote = RemotePlayer::connect(("127.0.0.1", port), RemoteConfig::default()).unwrap();
+    assert_ne!(play(remote), Notification::GameFinished { winner: PlayerId::NONE });
+    assert!(handle.join().unwrap().iter().any(|kind| kind == "bad_action"));
+    // Timeouts
+    let config = RemoteConfig { move_timeout: Duration::from_millis(1) };
+    let (port, _) = bot(|message| {
+        thread::sleep(Duration::from_millis(5));
+        Some(message["legal"][0].clone())
+    });
+    let remote = RemotePlayer::connect(("127.0.0.1", port), config).unwrap();
+    assert_ne!(play(remote), Notification::GameFinished { winner: PlayerId::NONE });
+    // Crash on the first move
+    let (port, _) = bot(|_| None);
+    let remote = RemotePlayer::connect(("127.0.0.1", port), RemoteConfig::default()).unwrap();
+    assert_ne!(play(remote), Notification::GameFinished { winner: PlayerId::NONE });
+}
+
+#[test]
+fn remote_disconnects_bots_not_reading() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (done, wait) = channel::<()>();
+    // Keeps the connection open without ever reading from it
+    let handle = thread::spawn(move || {
+        let (_stream, _) = listener.accept().unwrap();
+        let _ = wait.recv();
+    });
+    let config = RemoteConfig { move_timeout: Duration::from_millis(1) };
+    let mut remote = RemotePlayer::connect(("127.0.0.1", port), config).unwrap();
+    for _ in 0..1_000_000 {
+        remote.notify(&Notification::ThiefRolled);
+        if !remote.connected() {
+            break;
+        }
+    }
+    assert!(!remote.connected());
+    assert_ne!(play(remote), Notification::GameFinished { winner: PlayerId::NONE });
+    done.send(()).unwrap();
+    handle.join().unwrap();
+}
@@ -0,0 +1,50 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Scenario, Phase, TurnPhase, DevelopmentPhase, Action, Notification, Violation, legal};
+use crate::state::{PlayerId, TricellState, StateMaker};
+use crate::board::{setup, layout};
+use crate::board::utils::topology::Topology;
+use crate::player::Randomy;
+use crate::utils::{Coord, Resources};
+
+#[test]
+fn scenario_builds_playable_position() {
+    let mut rng = SmallRng::seed_from_u64(1);
+    let state = setup::random_default::<TricellState, SmallRng>(&mut rng, 2);
+    let first = layout::DEFAULT.intersections[0];
+    let last = *layout::DEFAULT.intersections.last().unwrap();
+    let first_road = state.intersection_path_neighbours(first).unwrap()[0];
+    let last_road = state.intersection_path_neighbours(last).unwrap()[0];
+
+    let phase = Phase::Turn { player: PlayerId::FIRST, turn_phase: TurnPhase::Free, development_phase: DevelopmentPhase::Ready };
+    let (mut state, phase) = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, first)
+        .road(PlayerId::FIRST, first_road)
+        .settlement(PlayerId::from(1u8), last)
+        .road(PlayerId::from(1u8), last_road)
+        .resources(PlayerId::FIRST, Resources::CITY)
+        .phase(phase)
+        .build()
+        .unwrap();
+    assert_eq!(state.get_bank_resources(), Resources::STARTING_BANK - Resources::CITY);
+    assert!(legal::legal(&phase, &state, Action::BuildCity { intersection: first }).is_ok());
+
+    let mut game = Game::new();
+    game.add_player(Box::new(Randomy::new_player()));
+    game.add_player(Box::new(Randomy::new_player()));
+    let notification = game.play_from(&mut rng, &mut state, phase, vec![0, 1]);
+    assert_ne!(notification, Notification::GameFinished { winner: PlayerId::NONE });
+}
+
+#[test]
+fn scenario_rejects_incoherent_position() {
+    let state = TricellState::new_empty(layout::DEFAULT.clone(), 2);
+    let intersection = state.hex_intersection_neighbours(Coord::ZERO).unwrap()[0];
+    let neighbour = state.intersection_intersection_neighbours(intersection).unwrap()[0];
+    let scenario = Scenario::from_state(state)
+        .settlement(PlayerId::FIRST, intersection)
+        .settlement(PlayerId::from(1u8), neighbour);
+    let violations = scenario.build().err().expect("Position should be incoherent");
+    assert!(violations.iter().any(|violation| matches!(violation, Violation::DistanceRule { intersection: _, neighbour: _ })));
+}
@@ -0,0 +1,78 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Phase, Action, to_notation, from_notation, legal};
+use crate::state::{TricellState, PlayerId, PlayerView};
+use crate::board::{setup, layout};
+use crate::board::symmetry::{Symmetry, canonicalize, permute};
+use crate::board::utils::topology::Topology;
+use crate::player::generate_possible_actions;
+use crate::utils::Coord;
+
+#[test]
+fn symmetries_form_a_group() {
+    let all = Symmetry::all();
+    assert_eq!(all.len(), Symmetry::COUNT);
+    assert_eq!(Symmetry::preserving(&layout::DEFAULT).len(), Symmetry::COUNT);
+    for symmetry in all.iter() {
+        assert_eq!(Symmetry::from_index(symmetry.index()), *symmetry);
+        let inverse = symmetry.inverse();
+        for path in layout::DEFAULT.paths.iter() {
+            assert_eq!(inverse.coord(symmetry.coord(*path)), *path);
+            // Neighbours stay neighbours
+            let mut images: Vec<_> = Coord::TOPOLOGY.path_intersection_neighbours(*path).unwrap()
+                .into_iter().map(|intersection| symmetry.coord(intersection)).collect();
+            let mut neighbours = Coord::TOPOLOGY.path_intersection_neighbours(symmetry.coord(*path)).unwrap().to_vec();
+            images.sort();
+            neighbours.sort();
+            assert_eq!(images, neighbours);
+        }
+    }
+    // Every symmetry is different
+    let probe = Coord::new(10, 2);
+    let mut images: Vec<_> = all.iter().map(|symmetry| symmetry.coord(probe)).collect();
+    images.sort();
+    images.dedup();
+    assert_eq!(images.len(), Symmetry::COUNT);
+}
+
+#[test]
+fn symmetric_states_keep_legality() {
+    let mut rng = SmallRng::seed_from_u64(3);
+    let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, 3);
+    let phase = Phase::START_GAME;
+    let mut actions = Vec::new();
+    generate_possible_actions(&mut actions, &PlayerView::new(&state, PlayerId::FIRST));
+    let mask: Vec<bool> = actions.iter().map(|action| legal::legal(&phase, &state, *action).is_ok()).collect();
+    let original = to_notation(&state, &phase).unwrap();
+    for symmetry in Symmetry::all() {
+        symmetry.apply_to_state(state.as_mut()).unwrap();
+        let permutation = symmetry.action_permutation(&actions).unwrap();
+        let moved_mask = permute(&permutation, &mask);
+        assert_eq!(symmetry.action_mask(&actions, &mask), Some(moved_mask.clone()));
+        for (index, action) in actions.iter().enumerate() {
+            assert_eq!(legal::legal(&phase, &state, *action).is_ok(), moved_mask[index]);
+        }
+        let road = actions.iter().position(|action| matches!(action, Action::BuildRoad { path: _ })).unwrap();
+        assert_eq!(symmetry.action_index(&actions, road), Some(permutation[road]));
+        assert_eq!(symmetry.action_index(&actions, actions.len()), None);
+        symmetry.inverse().apply_to_state(state.as_mut()).unwrap();
+        assert_eq!(to_notation(&state, &phase).unwrap(), original);
+    }
+}
+
+#[test]
+fn canonical_form_is_shared() {
+    let mut rng = SmallRng::seed_from_u64(4);
+    let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, 4);
+    let phase = Phase::START_TURNS;
+    canonicalize(&mut state, &phase);
+    let canonical = to_notation(&state, &phase).unwrap();
+    for symmetry in Symmetry::all() {
+        symmetry.apply_to_state(state.as_mut()).unwrap();
+        let mut moved = from_notation(&to_notation(&state, &phase).unwrap()).unwrap().0;
+        canonicalize(&mut moved, &phase);
+        assert_eq!(to_notation(&moved, &phase).unwrap(), canonical);
+        symmetry.inverse().apply_to_state(state.as_mut()).unwrap();
+    }
+}
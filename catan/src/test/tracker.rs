@@ -0,0 +1,93 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{Game, Scenario, Action, Error, Notification, Phase, TurnPhase, DevelopmentPhase};
+use crate::state::{TricellState, PlayerView, PlayerId};
+use crate::board::setup::BEGINNER;
+use crate::utils::{Coord, Resources};
+use crate::player::{CatanPlayer, HandTracker, Randomy};
+
+/// Random player checking that its tracker never loses the real hands
+struct TrackedRandomy {
+    player: Box<dyn CatanPlayer>,
+    tracker: Option<HandTracker>,
+}
+
+impl TrackedRandomy {
+    fn check(&self, view: &PlayerView) {
+        let state = view.state().unwrap();
+        let tracker = self.tracker.as_ref().unwrap();
+        for p in 0..view.player_count() {
+            let p = PlayerId::from(p);
+            let hand = state.get_player_hand(p);
+            // Discards are only removed from the hands once everyone chose
+            let mut resources = hand.resources;
+            for (discarding, discarded) in state.peek_discards().iter() {
+                if let (true, Some(discarded)) = (*discarding == p, discarded) {
+                    resources -= *discarded;
+                }
+            }
+            assert!(tracker.possible_hands(p).contains(&resources));
+            assert_eq!(tracker.development_card_count(p), hand.development_cards.total() + hand.new_development_cards.total());
+        }
+    }
+}
+
+impl CatanPlayer for TrackedRandomy {
+    fn new_game(&mut self, position: PlayerId, phase: &Phase, view: &PlayerView) {
+        self.tracker = Some(HandTracker::new(view, phase));
+        self.player.new_game(position, phase, view)
+    }
+
+    fn pick_action(&mut self, phase: &Phase, view: &PlayerView) -> Action {
+        self.check(view);
+        self.player.pick_action(phase, view)
+    }
+
+    fn bad_action(&mut self, error: Error) {
+        self.player.bad_action(error)
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        self.tracker.as_mut().unwrap().notify(notification);
+        self.player.notify(notification)
+    }
+
+    fn results(&mut self, view: &PlayerView, winner: PlayerId) {
+        self.check(view);
+        self.player.results(view, winner)
+    }
+
+    fn oracle(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn tracker_keeps_real_hands() {
+    for _ in 0..5 {
+        let mut game = Game::new();
+        for _ in 0..4 {
+            game.add_player(Box::new(TrackedRandomy { player: Box::new(Randomy::new_player()), tracker: None }));
+        }
+        game.setup_and_play();
+    }
+}
+
+#[test]
+fn tracker_charges_roads_after_the_initial_placement() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    // Right after the initial placement, the pieces left don't tell the phase apart
+    let (state, _) = BEGINNER.starting_position::<TricellState, SmallRng>(&mut rng, 2).unwrap();
+    let second = PlayerId::from(1u8);
+    let phase = Phase::Turn { player: second, turn_phase: TurnPhase::Free, development_phase: DevelopmentPhase::Ready };
+    let (state, phase) = Scenario::from_state(state)
+        .resources(second, Resources::ROAD)
+        .phase(phase)
+        .build()
+        .unwrap();
+    let mut tracker = HandTracker::new(&PlayerView::new(&state, PlayerId::FIRST), &phase);
+    assert_eq!(tracker.resource_count(second), 2);
+    tracker.notify(&Notification::ActionPlayed { by: second, action: Action::BuildRoad { path: Coord::new(1, 0) } });
+    assert_eq!(tracker.possible_hands(second), &[Resources::ZERO]);
+}
@@ -0,0 +1,34 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use crate::game::{validate, Phase, Violation};
+use crate::state::{PlayerId, TricellState};
+use crate::board::setup;
+use crate::board::utils::topology::Topology;
+use crate::utils::{Coord, Resource, Resources};
+
+#[test]
+fn validate_detects_tampering() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut state = setup::random_default::<TricellState, SmallRng>(&mut rng, 4);
+    assert_eq!(validate(&state, &Phase::START_GAME), vec![]);
+
+    state.get_player_hand_mut(PlayerId::FIRST).resources += Resources::new_one(Resource::Ore, 1);
+    assert_eq!(validate(&state, &Phase::START_GAME), vec![
+        Violation::ResourceCount { resource: Resource::Ore, bank: 19, players: 1 },
+    ]);
+    state.get_player_hand_mut(PlayerId::FIRST).resources = Resources::ZERO;
+
+    let intersection = state.hex_intersection_neighbours(Coord::ZERO).unwrap()[0];
+    let neighbour = state.intersection_intersection_neighbours(intersection).unwrap()[0];
+    for (player, coord) in [(0u8, intersection), (1u8, neighbour)].iter() {
+        let player = PlayerId::from(*player);
+        state.set_dynamic_intersection(*coord, player, false).unwrap();
+        let hand = state.get_player_hand_mut(player);
+        hand.settlement_pieces -= 1;
+        hand.building_vp += 1;
+    }
+    let violations = validate(&state, &Phase::START_GAME);
+    assert_eq!(violations.len(), 1);
+    assert!(matches!(violations[0], Violation::DistanceRule { intersection: _, neighbour: _ }));
+}
@@ -1,4 +1,7 @@
+use std::fmt::{self, Display};
 use std::ops::{Index, IndexMut, AddAssign};
+use rand::Rng;
+use rand::seq::SliceRandom;
 
 /******* DevelopmentCard *******/
 
@@ -31,6 +34,18 @@ impl DevelopmentCard {
     }
 }
 
+impl Display for DevelopmentCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            DevelopmentCard::Knight => write!(f, "K"),
+            DevelopmentCard::RoadBuilding => write!(f, "R"),
+            DevelopmentCard::YearOfPlenty => write!(f, "Y"),
+            DevelopmentCard::Monopole => write!(f, "M"),
+            DevelopmentCard::VictoryPoint => write!(f, "V"),
+        }
+    }
+}
+
 /******* DevelopmentCards *******/
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -43,6 +58,15 @@ pub struct DevelopmentCards {
 }
 
 impl DevelopmentCards {
+    /// The 25 cards of the standard deck
+    pub const STARTING_DECK: DevelopmentCards = DevelopmentCards {
+        knight: 14,
+        road_building: 2,
+        year_of_plenty: 2,
+        monopole: 2,
+        victory_point: 5,
+    };
+
     pub fn new() -> DevelopmentCards {
         DevelopmentCards {
             knight: 0,
@@ -101,3 +125,79 @@ impl AddAssign for DevelopmentCards {
         self.victory_point += other.victory_point;
     }
 }
+
+/******* DevelopmentDeck *******/
+
+/// Cards left to buy
+///
+/// Either an ordered stack, shuffled once at setup, or only the number of cards
+/// of each type, in which case the card is picked at random when bought
+#[derive(Clone, PartialEq, Debug)]
+pub enum DevelopmentDeck {
+    /// Stack drawn from the end
+    Ordered(Vec<DevelopmentCard>),
+    OnDemand(DevelopmentCards),
+}
+
+impl DevelopmentDeck {
+    /// Ordered deck containing the given cards in a random order
+    pub fn shuffled<R : Rng>(cards: DevelopmentCards, rng: &mut R) -> DevelopmentDeck {
+        let mut stack = Vec::new();
+        for card in DevelopmentCard::ALL.iter() {
+            for _ in 0..cards[*card] {
+                stack.push(*card);
+            }
+        }
+        stack.shuffle(rng);
+        DevelopmentDeck::Ordered(stack)
+    }
+
+    /// Number of cards of each type in the deck
+    pub fn cards(&self) -> DevelopmentCards {
+        match self {
+            DevelopmentDeck::Ordered(stack) => {
+                let mut cards = DevelopmentCards::new();
+                for card in stack.iter() {
+                    cards[*card] += 1;
+                }
+                cards
+            }
+            DevelopmentDeck::OnDemand(cards) => *cards,
+        }
+    }
+
+    pub fn size(&self) -> u8 {
+        match self {
+            DevelopmentDeck::Ordered(stack) => stack.len() as u8,
+            DevelopmentDeck::OnDemand(cards) => cards.total(),
+        }
+    }
+
+    /// Next card to be drawn, only known for ordered decks
+    pub fn peek(&self) -> Option<DevelopmentCard> {
+        match self {
+            DevelopmentDeck::Ordered(stack) => stack.last().copied(),
+            DevelopmentDeck::OnDemand(_) => None,
+        }
+    }
+
+    pub fn draw<R : Rng>(&mut self, rng: &mut R) -> Option<DevelopmentCard> {
+        match self {
+            DevelopmentDeck::Ordered(stack) => stack.pop(),
+            DevelopmentDeck::OnDemand(cards) => {
+                if cards.total() == 0 {
+                    return None;
+                }
+                let mut picked = rng.gen_range(0, cards.total());
+                for card in DevelopmentCard::ALL.iter() {
+                    if picked < cards[*card] {
+                        cards[*card] -= 1;
+                        return Some(*card);
+                    }
+                    picked -= cards[*card];
+                }
+                None
+            }
+        }
+    }
+}
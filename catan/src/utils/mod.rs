@@ -1,11 +1,13 @@
 mod development_card;
 mod resource;
 
-pub use development_card::{DevelopmentCard, DevelopmentCards};
+pub use development_card::{DevelopmentCard, DevelopmentCards, DevelopmentDeck};
 pub use resource::{Resource, Resources};
 pub use crate::board::{Coord, CoordType};
 pub use crate::state::PlayerId;
 
+use serde::{Serialize, Deserialize};
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Empty;
 
@@ -25,7 +27,7 @@ pub enum LandHex {
     Desert,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Harbor {
     None,
     Generic,
@@ -42,6 +44,14 @@ impl Hex {
             _ => None,
         }
     }
+
+    /// Number of the 36 dice combinations that make this hex produce
+    pub fn pips(&self) -> u8 {
+        match self.get_num() {
+            Some(num) if (2..=12).contains(&num) && num != 7 => 6 - (7 - num as i8).unsigned_abs(),
+            _ => 0,
+        }
+    }
 }
 
 impl Harbor {
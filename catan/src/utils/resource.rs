@@ -2,10 +2,11 @@ use std::fmt::{self, Display};
 use std::ops::{Add, Sub, AddAssign, SubAssign, Index, IndexMut};
 use std::cmp::Ordering;
 use std::convert::TryFrom;
+use serde::{Serialize, Deserialize};
 
 /******* Resource *******/
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Resource {
     Brick = 0,
     Lumber = 1,
@@ -1,6 +1,7 @@
 use ndarray::Array1;
 use pyo3::prelude::*;
 use pyo3::types::PyTuple;
+use pyo3::exceptions::ValueError;
 use numpy::convert::IntoPyArray;
 use std::thread;
 use std::sync::mpsc::{channel, Sender, Receiver};
@@ -10,7 +11,7 @@ use rand::seq::SliceRandom;
 
 use catan::game::Game;
 use catan::state::State;
-use catan::player::Randomy;
+use catan::player::{CatanPlayer, Randomy, Greedy};
 use catan::board::setup::random_default_setup_existing_state;
 use catan::board::layout;
 use super::{PythonState, PyCatanObservation, PyObservationFormat, PythonPlayer};
@@ -32,6 +33,16 @@ fn to_py_tuple(py: Python, hidden_state: bool, observation: Option<(u8, PyCatanO
     }.to_object(py)
 }
 
+/// Built-in players that can be picked as opponents
+const OPPONENTS: [&str; 2] = ["random", "greedy"];
+
+fn new_opponent(name: &str) -> Box<dyn CatanPlayer> {
+    match name {
+        "greedy" => Box::new(Greedy::new_player()),
+        _ => Box::new(Randomy::new_player()),
+    }
+}
+
 #[pyclass]
 pub struct SingleEnvironment {
     action_sender: Sender<u16>,
@@ -45,29 +56,33 @@ pub struct SingleEnvironment {
 impl SingleEnvironment {
 
     #[staticmethod]
-    #[args(format, opponents = 2)]
-    fn new(format: &PyObservationFormat, opponents: usize) -> SingleEnvironment {
+    #[args(format, opponents = 2, opponent = "\"random\"")]
+    fn new(format: &PyObservationFormat, opponents: usize, opponent: &str) -> PyResult<SingleEnvironment> {
+        if !OPPONENTS.contains(&opponent) {
+            return Err(ValueError::py_err(format!("Unknown opponent {:?}, expected one of {:?}", opponent, OPPONENTS)));
+        }
         let format = *format;
+        let opponent = String::from(opponent);
         let (action_sender, action_receiver) = channel();
         let (observation_sender, observation_receiver) = channel();
         let (result_sender, result_receiver) = channel();
         let game_thread = thread::spawn(move || {
             let mut game = Game::new();
             for _ in 0..opponents {
-                game.add_player(Box::new(Randomy::new_player()));
+                game.add_player(new_opponent(&opponent));
             };
             game.add_player(Box::new(PythonPlayer::new(0, format, action_receiver, observation_sender, result_sender)));
             loop {
                 game.setup_and_play();
             }
         });
-        SingleEnvironment {
+        Ok(SingleEnvironment {
             action_sender,
             observation_receiver,
             result_receiver,
             game_thread,
             include_hidden: format.include_hidden,
-        }
+        })
     }
 
     fn start(&mut self, py: Python) -> PyResult<PyObject> {
@@ -125,7 +140,7 @@ impl MultiEnvironment {
             };
             let mut rng = SmallRng::from_entropy();
             loop {
-                let mut state = PythonState::new(&layout::DEFAULT, players as u8, format);
+                let mut state = PythonState::new(layout::DEFAULT.clone(), players as u8, format);
                 random_default_setup_existing_state::<PythonState, SmallRng>(&mut rng, &mut state);
                 let mut players_order: Vec<usize> = (0..players).collect();
                 players_order.shuffle(&mut rng);
@@ -3,6 +3,9 @@ mod python_state;
 mod python_player;
 mod py_catan_observation;
 mod py_observation_format;
+mod py_symmetry;
+mod py_evaluation;
+mod py_action_space;
 
 use pyo3::prelude::*;
 
@@ -17,6 +20,9 @@ fn pycatan(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<SingleEnvironment>()?;
     m.add_class::<MultiEnvironment>()?;
     m.add_class::<PyObservationFormat>()?;
+    py_symmetry::register(m)?;
+    py_evaluation::register(m)?;
+    py_action_space::register(m)?;
 
     Ok(())
 }
@@ -0,0 +1,67 @@
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use pyo3::exceptions::ValueError;
+
+use catan::board::layout;
+use catan::state::PlayerId;
+use catan::player::{ActionSpace, ACTION_SPACE_VERSION};
+
+/// Indices of the actions of a seat on the default layout, see `ACTION_SPACE_VERSION`
+#[pyclass]
+pub struct PyActionSpace {
+    space: ActionSpace,
+}
+
+#[pymethods]
+impl PyActionSpace {
+    #[getter]
+    fn version(&self) -> u32 {
+        self.space.version()
+    }
+
+    fn size(&self) -> usize {
+        self.space.len()
+    }
+
+    /// Human readable name of the action at an index
+    fn name(&self, index: usize) -> PyResult<String> {
+        self.space.name(index)
+            .ok_or_else(|| ValueError::py_err(format!("No action {} among {}", index, self.space.len())))
+    }
+
+    fn names(&self) -> Vec<String> {
+        (0..self.space.len()).filter_map(|index| self.space.name(index)).collect()
+    }
+
+    /// Index of the action with the given name
+    fn index(&self, name: &str) -> PyResult<usize> {
+        self.space.index_of_name(name)
+            .ok_or_else(|| ValueError::py_err(format!("Unknown action {:?}", name)))
+    }
+
+    /// Name, start and end of each section
+    fn sections(&self) -> Vec<(String, usize, usize)> {
+        self.space.sections().iter()
+            .map(|section| (String::from(section.name), section.start, section.end()))
+            .collect()
+    }
+}
+
+/// Action space of a seat on the default layout, as used by the environments
+#[pyfunction(players = 4, seat = 0)]
+pub fn action_space(players: u8, seat: u8) -> PyResult<PyActionSpace> {
+    if seat >= players {
+        return Err(ValueError::py_err(format!("No seat {} in a {} player game", seat, players)));
+    }
+    Ok(PyActionSpace {
+        space: ActionSpace::new(&layout::DEFAULT, players, PlayerId::from(seat)),
+    })
+}
+
+/// Adds the action space to the python module
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add("ACTION_SPACE_VERSION", ACTION_SPACE_VERSION)?;
+    m.add_class::<PyActionSpace>()?;
+    m.add_wrapped(wrap_pyfunction!(action_space))?;
+    Ok(())
+}
@@ -2,7 +2,7 @@ use ndarray::{Array1, Array3, ArrayD, ArrayViewD, ArrayViewMutD};
 use pyo3::prelude::*;
 use numpy::{IntoPyArray, PyArrayDyn};
 
-use catan::state::{State, PlayerHand, PlayerId};
+use catan::state::{State, PlayerView, PlayerHand, PublicHand, PlayerId};
 use catan::utils::{Hex, LandHex, Harbor, Resource, DevelopmentCard};
 use catan::game::{Phase, TurnPhase, DevelopmentPhase};
 use catan::player::relative;
@@ -77,20 +77,21 @@ pub(crate) struct PyCatanObservation {
 }
 
 impl PyCatanObservation {
-    pub fn generate_board(format: PyObservationFormat, player: PlayerId, state: &State) -> Array3<i32> {
-        let player_count = state.player_count();
+    pub fn generate_board(format: PyObservationFormat, view: &PlayerView) -> Array3<i32> {
+        let player = view.player();
+        let player_count = view.player_count();
         let mut board = Array3::<i32>::zeros((format.width,format.height, 13 + 2 * player_count as usize));
-        let layout = state.get_layout();
+        let layout = view.get_layout();
         // ## Hexes [0,7[
         for coord in layout.hexes.iter() {
-            let hex = state.get_static_hex(*coord).unwrap();
+            let hex = view.get_static_hex(*coord).unwrap();
             if let Hex::Land(hex) = hex {
                 let (x,y) = format.map(*coord);
                 match hex {
                     LandHex::Desert => { board[(x, y, 5)] = 1; },
                     LandHex::Prod(res, num) => { board[(x, y, res.to_usize())] = num.into(); },
                 }
-                if *coord == state.get_thief_hex() {
+                if *coord == view.get_thief_hex() {
                     board[(x, y, 6)] = 1;
                 }
             }
@@ -98,7 +99,7 @@ impl PyCatanObservation {
         // ## Paths [7,7+player_count[
         let c = 7;
         for coord in layout.paths.iter() {
-            let path = state.get_dynamic_path(*coord).unwrap();
+            let path = view.get_dynamic_path(*coord).unwrap();
             if let Some(p) = path {
                 let p = relative::player_id_to_relative(player, p, player_count);
                 let (x,y) = format.map(*coord);
@@ -110,13 +111,13 @@ impl PyCatanObservation {
         // ## Intersections [7+player_count, 13+2×player_count[
         for coord in layout.intersections.iter() {
             let (x,y) = format.map(*coord);
-            let harbor = state.get_static_harbor(*coord).unwrap();
+            let harbor = view.get_static_harbor(*coord).unwrap();
             match harbor {
                 Harbor::Generic => { board[(x, y, c_harbor + 5)] = 1; }
                 Harbor::Special(res) => { board[(x, y, c_harbor + res.to_usize())] = 1; }
                 _ => (),
             }
-            let intersection = state.get_dynamic_intersection(*coord).unwrap();
+            let intersection = view.get_dynamic_intersection(*coord).unwrap();
             if let Some((p, is_city)) = intersection {
                 let p = relative::player_id_to_relative(player, p, player_count);
                 board[(x, y, c_buildings + p.to_usize())] = if is_city { 2 } else { 1 };
@@ -149,45 +150,46 @@ impl PyCatanObservation {
     }
 
     // Fills 8 cells
-    pub fn fill_flat_concealed(array: &mut Array1::<i32>, index: usize, hand: &PlayerHand, has_longest_road: bool, has_largest_army: bool) {
-        array[index] = hand.resources.total().into();
+    pub fn fill_flat_concealed(array: &mut Array1::<i32>, index: usize, hand: &PublicHand, has_longest_road: bool, has_largest_army: bool) {
+        array[index] = hand.resource_count.into();
         array[index + 1] = hand.road_pieces.into();
         array[index + 2] = hand.settlement_pieces.into();
         array[index + 3] = hand.city_pieces.into();
         array[index + 4] = hand.knights.into();
-        array[index + 5] = hand.development_cards.total().into();
+        array[index + 5] = hand.development_card_count.into();
         array[index + 6] = if has_longest_road { 1 } else { 0 };
         array[index + 7] = if has_largest_army { 1 } else { 0 };
     }
 
-    pub fn generate_flat(player: PlayerId, state: &State, phase: &Phase) -> Array1<i32> {
-        let player_count = state.player_count();
+    pub fn generate_flat(view: &PlayerView, phase: &Phase) -> Array1<i32> {
+        let player = view.player();
+        let player_count = view.player_count();
         let mut flat = Array1::<i32>::zeros(29+(player_count as usize)*8);
-        let longest_road = match state.get_longest_road() {
+        let longest_road = match view.get_longest_road() {
             None => PlayerId::NONE,
             Some((player_id, _)) => player_id,
         };
-        let largest_army = match state.get_largest_army() {
+        let largest_army = match view.get_largest_army() {
             None => PlayerId::NONE,
             Some((player_id, _)) => player_id,
         };
         // ## Player 27
-        let hand = &state.get_player_hand(player);
+        let hand = view.get_hand();
         PyCatanObservation::fill_flat_visible(&mut flat, 0, hand, longest_road == player, largest_army == player);
         // ## Opponents (p-1)*8
         for opp in 1..player_count {
             let player_index = 19+(opp as usize)*8;
             let player = relative::offset_to_player_id(player, opp, player_count);
-            let hand = &state.get_player_hand(player);
-            PyCatanObservation::fill_flat_concealed(&mut flat, player_index, hand, longest_road == player, largest_army == player);
+            let hand = view.get_public_hand(player);
+            PyCatanObservation::fill_flat_concealed(&mut flat, player_index, &hand, longest_road == player, largest_army == player);
         }
         // ## State 6
         let c_state = 19+(player_count as usize)*8;
-        let bank_resources = state.get_bank_resources();
+        let bank_resources = view.get_bank_resources();
         for res in 0..Resource::COUNT {
             flat[c_state + res] = bank_resources[res].into();
         }
-        flat[c_state+5] = state.get_development_cards().total().into();
+        flat[c_state+5] = view.get_development_deck_size().into();
         // ## Phase 4
         let c_phase = c_state + 6;
         if let Phase::Turn { player: _, turn_phase, development_phase } = phase {
@@ -216,21 +218,22 @@ impl PyCatanObservation {
             let player_index = (opp as usize - 1)*27;
             let player = relative::offset_to_player_id(player, opp, player_count);
             let hand = &state.get_player_hand(player);
-            PyCatanObservation::fill_flat_concealed(&mut hidden, player_index, hand, longest_road == player, largest_army == player);
+            PyCatanObservation::fill_flat_visible(&mut hidden, player_index, hand, longest_road == player, largest_army == player);
         };
         hidden
     }
 
-    pub(crate) fn new_array(format: PyObservationFormat, player: PlayerId, state: &State, phase: &Phase, legal_actions: Array1<bool>) -> PyCatanObservation {
+    pub(crate) fn new_array(format: PyObservationFormat, view: &PlayerView, phase: &Phase, legal_actions: Array1<bool>) -> PyCatanObservation {
         // # BOARD
-        let board = PyCatanObservation::generate_board(format, player, state);
+        let board = PyCatanObservation::generate_board(format, view);
 
         // # FLAT
-        let flat = PyCatanObservation::generate_flat(player, state, phase);
+        let flat = PyCatanObservation::generate_flat(view, phase);
 
         // # HIDDEN
         let hidden = if format.include_hidden {
-            Some(PyCatanObservation::generate_hidden(player, state, phase))
+            let state = view.state().expect("Hidden information requires an oracle view");
+            Some(PyCatanObservation::generate_hidden(view.player(), state, phase))
         } else {
             None
         };
@@ -244,16 +247,17 @@ impl PyCatanObservation {
         }
     }
 
-    pub(crate) fn new_python_array(format: PyObservationFormat, player: PlayerId, py_state: &PythonState, state: &State, phase: &Phase, legal_actions: Array1<bool>) -> PyCatanObservation {
+    pub(crate) fn new_python_array(format: PyObservationFormat, py_state: &PythonState, view: &PlayerView, phase: &Phase, legal_actions: Array1<bool>) -> PyCatanObservation {
         // # BOARD
-        let board = py_state.boards[player.to_usize()].clone();
+        let board = py_state.boards[view.player().to_usize()].clone();
 
         // # FLAT
-        let flat = PyCatanObservation::generate_flat(player, state, phase);
+        let flat = PyCatanObservation::generate_flat(view, phase);
 
         // # HIDDEN
         let hidden = if format.include_hidden {
-            Some(PyCatanObservation::generate_hidden(player, state, phase))
+            let state = view.state().expect("Hidden information requires an oracle view");
+            Some(PyCatanObservation::generate_hidden(view.player(), state, phase))
         } else {
             None
         };
@@ -0,0 +1,53 @@
+use ndarray::Array1;
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use pyo3::exceptions::ValueError;
+
+use catan::game::from_notation;
+use catan::state::{State, PlayerId};
+use catan::player::evaluation::{Evaluation, EvaluationWeights, FEATURES, evaluate as evaluate_state, evaluate_relative};
+
+fn read_position(notation: &str, player: u8) -> PyResult<State> {
+    let (state, _) = from_notation(notation)
+        .map_err(|error| ValueError::py_err(format!("Invalid notation: {:?}", error)))?;
+    if player >= state.player_count() {
+        return Err(ValueError::py_err(format!("No player {} in a {} player game", player, state.player_count())));
+    }
+    Ok(state)
+}
+
+/// Features of a position for one player, in the order of `EVALUATION_FEATURES`
+#[pyfunction]
+pub fn evaluation_features(py: Python, notation: &str, player: u8) -> PyResult<Py<PyArray1<f32>>> {
+    let state = read_position(notation, player)?;
+    let features = Evaluation::new(state.as_ref(), PlayerId::from(player)).to_vec();
+    Ok(Array1::from(features).into_pyarray(py).to_owned())
+}
+
+/// How good a position is for one player, or how much better than for its best opponent
+///
+/// Weights are given in the order of `EVALUATION_FEATURES`, the default ones are used otherwise
+#[pyfunction(weights = "None", relative = false)]
+pub fn evaluate(notation: &str, player: u8, weights: Option<Vec<f32>>, relative: bool) -> PyResult<f32> {
+    let state = read_position(notation, player)?;
+    let weights = match weights {
+        Some(weights) => EvaluationWeights::from_vec(&weights)
+            .ok_or_else(|| ValueError::py_err(format!("Expected {} weights", FEATURES.len())))?,
+        None => EvaluationWeights::default(),
+    };
+    let player = PlayerId::from(player);
+    Ok(if relative {
+        evaluate_relative(state.as_ref(), player, &weights)
+    } else {
+        evaluate_state(state.as_ref(), player, &weights)
+    })
+}
+
+/// Adds the evaluation functions to the python module
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add("EVALUATION_FEATURES", FEATURES.to_vec())?;
+    m.add_wrapped(wrap_pyfunction!(evaluation_features))?;
+    m.add_wrapped(wrap_pyfunction!(evaluate))?;
+    Ok(())
+}
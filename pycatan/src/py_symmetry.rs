@@ -0,0 +1,75 @@
+use ndarray::{Array1, Array3};
+use numpy::{IntoPyArray, PyArray1, PyArray3};
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use pyo3::exceptions::ValueError;
+
+use catan::board::layout;
+use catan::board::symmetry::{Symmetry, permute};
+use catan::game::Action;
+use catan::state::PlayerId;
+use catan::player::ActionSpace;
+
+use super::PyObservationFormat;
+
+fn possible_actions(players: u8) -> Vec<Action> {
+    ActionSpace::new(&layout::DEFAULT, players, PlayerId::FIRST).actions().to_vec()
+}
+
+fn symmetry(index: usize) -> PyResult<Symmetry> {
+    if index >= Symmetry::COUNT {
+        return Err(ValueError::py_err(format!("No symmetry {} among {}", index, Symmetry::COUNT)));
+    }
+    Ok(Symmetry::from_index(index))
+}
+
+/// Board observation seen through one of the 12 symmetries
+#[pyfunction]
+pub fn symmetric_board(py: Python, format: &PyObservationFormat, board: &PyArray3<i32>, symmetry: usize) -> PyResult<Py<PyArray3<i32>>> {
+    let board = board.as_array();
+    let symmetry = self::symmetry(symmetry)?;
+    let mut result = Array3::<i32>::zeros(board.dim());
+    let layout = &layout::DEFAULT;
+    for coord in layout.hexes.iter().chain(layout.paths.iter()).chain(layout.intersections.iter()) {
+        let (x, y) = format.map(*coord);
+        let (image_x, image_y) = format.map(symmetry.coord(*coord));
+        for channel in 0..board.dim().2 {
+            result[(image_x, image_y, channel)] = board[(x, y, channel)];
+        }
+    }
+    Ok(result.into_pyarray(py).to_owned())
+}
+
+/// Legal actions array seen through one of the 12 symmetries
+#[pyfunction]
+pub fn symmetric_actions(py: Python, players: u8, actions: &PyArray1<bool>, symmetry: usize) -> PyResult<Py<PyArray1<bool>>> {
+    let possible_actions = possible_actions(players);
+    let mask = actions.as_array().to_vec();
+    if mask.len() != possible_actions.len() {
+        return Err(ValueError::py_err(format!("Expected {} actions, got {}", possible_actions.len(), mask.len())));
+    }
+    let permutation = self::symmetry(symmetry)?.action_permutation(&possible_actions)
+        .expect("Default layout is symmetric");
+    Ok(Array1::from(permute(&permutation, &mask)).into_pyarray(py).to_owned())
+}
+
+/// Action index seen through one of the 12 symmetries
+#[pyfunction]
+pub fn symmetric_action(players: u8, action: u16, symmetry: usize) -> PyResult<u16> {
+    let possible_actions = possible_actions(players);
+    if action as usize >= possible_actions.len() {
+        return Err(ValueError::py_err(format!("No action {} among {}", action, possible_actions.len())));
+    }
+    let index = self::symmetry(symmetry)?.action_index(&possible_actions, action as usize)
+        .expect("Default layout is symmetric");
+    Ok(index as u16)
+}
+
+/// Adds the symmetry functions to the python module
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add("SYMMETRY_COUNT", Symmetry::COUNT)?;
+    m.add_wrapped(wrap_pyfunction!(symmetric_board))?;
+    m.add_wrapped(wrap_pyfunction!(symmetric_actions))?;
+    m.add_wrapped(wrap_pyfunction!(symmetric_action))?;
+    Ok(())
+}
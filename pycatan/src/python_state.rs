@@ -1,8 +1,9 @@
 use std::any::Any;
+use std::sync::Arc;
 use ndarray::{Array1, Array3};
 
 use catan::board::{Layout, Error};
-use catan::utils::{Hex, LandHex, Harbor, Coord, DevelopmentCards, Resources};
+use catan::utils::{Hex, LandHex, Harbor, Coord, DevelopmentDeck, Resources};
 use catan::state::PlayerHand;
 use catan::state::{State, TricellState, StateTrait, StateMaker, PlayerId};
 
@@ -16,7 +17,7 @@ pub struct PythonState {
 }
 
 impl PythonState {
-    pub fn new(layout: &'static Layout, players: u8, format: PyObservationFormat) -> PythonState {
+    pub fn new(layout: Arc<Layout>, players: u8, format: PyObservationFormat) -> PythonState {
         let player_count = players as usize;
         PythonState {
             format,
@@ -52,11 +53,13 @@ impl PythonState {
 impl StateTrait for PythonState {
     fn get_layout(&self) -> &Layout { self.state.get_layout() }
 
+    fn get_shared_layout(&self) -> &Arc<Layout> { self.state.get_shared_layout() }
+
     fn player_count(&self) -> u8 { self.state.player_count() }
 
-    fn get_development_cards(&self) -> DevelopmentCards { self.state.get_development_cards() }
+    fn get_development_deck(&self) -> &DevelopmentDeck { self.state.get_development_deck() }
 
-    fn get_development_cards_mut(&mut self) -> &mut DevelopmentCards { self.state.get_development_cards_mut() }
+    fn get_development_deck_mut(&mut self) -> &mut DevelopmentDeck { self.state.get_development_deck_mut() }
 
     fn get_bank_resources(&self) -> Resources { self.state.get_bank_resources() }
 
@@ -94,6 +97,10 @@ impl StateTrait for PythonState {
 
     fn update_largest_army(&mut self, player: PlayerId) { self.state.update_largest_army(player) }
 
+    fn set_longest_road(&mut self, holder: Option<PlayerId>) { self.state.set_longest_road(holder) }
+
+    fn set_largest_army(&mut self, holder: Option<PlayerId>) { self.state.set_largest_army(holder) }
+
     // --- static board --- //
 
     fn set_static_hex(&mut self, coord: Coord, hex: Hex) -> Result<(), Error> {
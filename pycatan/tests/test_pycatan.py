@@ -0,0 +1,77 @@
+"""Smoke tests of the python bindings
+
+With the `pycatan` module built and importable, run `python -m unittest discover pycatan/tests`
+"""
+
+import unittest
+
+import pycatan
+
+# Beginner board with four players, before the initial placement
+BEGINNER = (
+    "B5,G6,W11,L8,O3,G4,W5,G9,L11,D,L3,O8,G12,B6,W4,B10,O10,W2,L9 "
+    "0=X,1=X,3=L,4=L,7=G,14=B,15=B,17=G,26=X,28=O,37=X,38=O,45=X,46=X,47=X,48=X,50=W,51=W "
+    "- - 9 "
+    + "/".join(["0,0,0,0,0:0,0,0,0,0:0,0,0,0,0:0:0"] * 4)
+    + " 19,19,19,19,19 14,2,2,2,5 -,- i0:1:s -"
+)
+
+
+class EvaluationTest(unittest.TestCase):
+    def test_evaluate(self):
+        self.assertIsInstance(pycatan.evaluate(BEGINNER, 0), float)
+        self.assertIsInstance(pycatan.evaluate(BEGINNER, 0, relative=True), float)
+        weights = [1.0] * len(pycatan.EVALUATION_FEATURES)
+        self.assertIsInstance(pycatan.evaluate(BEGINNER, 0, weights=weights), float)
+
+    def test_evaluate_rejects_bad_input(self):
+        with self.assertRaises(ValueError):
+            pycatan.evaluate("", 0)
+        with self.assertRaises(ValueError):
+            pycatan.evaluate(BEGINNER, 7)
+        with self.assertRaises(ValueError):
+            pycatan.evaluate(BEGINNER, 0, weights=[1.0])
+
+    def test_evaluation_features(self):
+        features = pycatan.evaluation_features(BEGINNER, 0)
+        self.assertEqual(len(features), len(pycatan.EVALUATION_FEATURES))
+
+
+class ActionSpaceTest(unittest.TestCase):
+    def test_action_space(self):
+        space = pycatan.action_space()
+        self.assertEqual(space.version, pycatan.ACTION_SPACE_VERSION)
+        names = space.names()
+        self.assertEqual(len(names), space.size())
+        self.assertEqual(space.index(names[-1]), space.size() - 1)
+        self.assertEqual(space.name(0), names[0])
+        sections = space.sections()
+        self.assertEqual(sections[0][1], 0)
+        self.assertEqual(sections[-1][2], space.size())
+
+    def test_action_space_rejects_bad_input(self):
+        with self.assertRaises(ValueError):
+            pycatan.action_space(players=2, seat=2)
+        with self.assertRaises(ValueError):
+            pycatan.action_space().name(100000)
+        with self.assertRaises(ValueError):
+            pycatan.action_space().index("Fly")
+
+
+class SymmetryTest(unittest.TestCase):
+    def test_symmetric_action(self):
+        size = pycatan.action_space().size()
+        self.assertEqual(pycatan.symmetric_action(4, 0, 0), 0)
+        for symmetry in range(pycatan.SYMMETRY_COUNT):
+            self.assertLess(pycatan.symmetric_action(4, size - 1, symmetry), size)
+
+    def test_symmetric_action_rejects_bad_input(self):
+        size = pycatan.action_space().size()
+        with self.assertRaises(ValueError):
+            pycatan.symmetric_action(4, size, 0)
+        with self.assertRaises(ValueError):
+            pycatan.symmetric_action(4, 0, pycatan.SYMMETRY_COUNT)
+
+
+if __name__ == "__main__":
+    unittest.main()